@@ -3,142 +3,194 @@ use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 
+/// Element names of the vector types, in index order
+const AXES: [char; 4] = ['x', 'y', 'z', 'w'];
+/// Colour-style aliases for the element names, in index order
+const COLOURS: [char; 4] = ['r', 'g', 'b', 'a'];
+
 fn main() {
     let out_dir = env::var("OUT_DIR").unwrap();
 
-    write_vec2_swizzle(Path::new(&out_dir));
-    write_vec3_swizzle(Path::new(&out_dir));
-    write_vec4_swizzle(Path::new(&out_dir));
-}
-
-fn write_vec2_swizzle(path: &Path) {
-    let axes = ['x', 'y'];
+    write_vec_swizzle(Path::new(&out_dir), 2);
+    write_vec_swizzle(Path::new(&out_dir), 3);
+    write_vec_swizzle(Path::new(&out_dir), 4);
 
-    let path = path.join("vec2_swizzle.rs");
+    write_vec_swizzle_tests(Path::new(&out_dir), 2);
+    write_vec_swizzle_tests(Path::new(&out_dir), 3);
+    write_vec_swizzle_tests(Path::new(&out_dir), 4);
+}
 
-    let mut f = File::create(&path).unwrap();
+/// Returns every way of picking `len` of the indices below `dim`, either
+/// allowing the same index more than once or not
+fn picks(dim: usize, len: usize, repeat: bool) -> Vec<Vec<usize>> {
+    let mut out = vec![vec![]];
+
+    for _ in 0..len {
+        let mut next = Vec::new();
+        for p in out.iter() {
+            for i in 0..dim {
+                if repeat || !p.contains(&i) {
+                    let mut p = p.clone();
+                    p.push(i);
+                    next.push(p);
+                }
+            }
+        }
+        out = next;
+    }
 
-    write!(f, "/// Swizzle-like functions for generic Vec2 (GENERATED)\n").unwrap();
-    write!(f, "impl<T> Vec2<T> where T: Copy {{\n").unwrap();
+    out
+}
 
-    // one
-    for x in axes.iter() {
-        write!(f, "\t/// Returns {}\n", x).unwrap();
-        write!(f, "\tpub fn {}(&self) -> T {{\n", x).unwrap();
-        write!(f, "\t\tself.{}\n", x).unwrap();
-        write!(f, "\t}}\n").unwrap();
-    }
+/// Names a pick using `names` (e.g. [2, 0] with AXES is "zx")
+fn name(names: &[char], pick: &[usize]) -> String {
+    pick.iter().map(|&i| names[i]).collect()
+}
 
-    // two
-    for x in axes.iter() {
-        for y in axes.iter() {
-            write!(f, "\t/// Returns new Vec2 with x set to `{}` and y set to `{}`\n", x, y).unwrap();
-            write!(f, "\tpub fn {}{}(&self) -> Self {{\n", x, y).unwrap();
-            write!(f, "\t\tVec2::from(self.{}, self.{})\n", x, y).unwrap();
-            write!(f, "\t}}\n").unwrap();
-        }
+/// Joins items as "a", "a and b" or "a, b and c"
+fn join(items: &[String]) -> String {
+    match items.len() {
+        0 => String::new(),
+        1 => items[0].clone(),
+        n => format!("{} and {}", items[..n - 1].join(", "), items[n - 1])
     }
-
-    write!(f, "}}\n").unwrap();
 }
 
-fn write_vec3_swizzle(path: &Path) {
-    let axes = ['x', 'y', 'z'];
+/// Values given to the elements in the generated tests
+fn value(i: usize) -> i32 {
+    (i as i32 + 1)*10
+}
 
-    let path = path.join("vec3_swizzle.rs");
+fn write_vec_swizzle(path: &Path, dim: usize) {
+    let path = path.join(format!("vec{}_swizzle.rs", dim));
 
     let mut f = File::create(&path).unwrap();
 
-    write!(f, "/// Swizzle-like functions for generic Vec3 (GENERATED)\n").unwrap();
-    write!(f, "impl<T> Vec3<T> where T: Copy {{\n").unwrap();
+    writeln!(f, "/// Swizzle-like functions for generic Vec{} (GENERATED)", dim).unwrap();
+    writeln!(f, "impl<T> Vec{}<T> where T: Copy {{", dim).unwrap();
 
-    // one
-    for x in axes.iter() {
-        write!(f, "\t/// Returns {}\n", x).unwrap();
-        write!(f, "\tpub fn {}(&self) -> T {{\n", x).unwrap();
-        write!(f, "\t\tself.{}\n", x).unwrap();
-        write!(f, "\t}}\n").unwrap();
-    }
+    for names in [&AXES[..dim], &COLOURS[..dim]].iter() {
+        // one
+        for i in 0..dim {
+            writeln!(f, "\t/// Returns {}", AXES[i]).unwrap();
+            writeln!(f, "\tpub fn {}(&self) -> T {{", names[i]).unwrap();
+            writeln!(f, "\t\tself.{}", AXES[i]).unwrap();
+            writeln!(f, "\t}}").unwrap();
+        }
 
-    // two
-    for x in axes.iter() {
-        for y in axes.iter() {
-            write!(f, "\t/// Returns new Vec2 with x set to `{}` and y set to `{}`\n", x, y).unwrap();
-            write!(f, "\tpub fn {}{}(&self) -> super::Vec2<T> {{\n", x, y).unwrap();
-            write!(f, "\t\tsuper::Vec2::from(self.{}, self.{})\n", x, y).unwrap();
-            write!(f, "\t}}\n").unwrap();
+        // two or more
+        for len in 2..dim + 1 {
+            for pick in picks(dim, len, true).iter() {
+                let sets: Vec<String> = pick.iter().enumerate()
+                    .map(|(j, &i)| format!("{} set to `{}`", AXES[j], AXES[i]))
+                    .collect();
+                let args: Vec<String> = pick.iter()
+                    .map(|&i| format!("self.{}", AXES[i]))
+                    .collect();
+
+                writeln!(f, "\t/// Returns new Vec{} with {}", len, join(&sets)).unwrap();
+                writeln!(f, "\tpub fn {}(&self) -> super::Vec{}<T> {{", name(names, pick), len).unwrap();
+                writeln!(f, "\t\tsuper::Vec{}::from({})", len, args.join(", ")).unwrap();
+                writeln!(f, "\t}}").unwrap();
+            }
         }
-    }
 
-    // three
-    for x in axes.iter() {
-        for y in axes.iter() {
-            for z in axes.iter() {
-                write!(f, "\t/// Returns new Vec3 with x set to `{}`, y set to `{}` and z set to `{}`\n", x, y, z).unwrap();
-                write!(f, "\tpub fn {}{}{}(&self) -> super::Vec3<T> {{\n", x, y, z).unwrap();
-                write!(f, "\t\tsuper::Vec3::from(self.{}, self.{}, self.{})\n", x, y, z).unwrap();
-                write!(f, "\t}}\n").unwrap();
+        // setters
+        for len in 2..dim + 1 {
+            for pick in picks(dim, len, false).iter() {
+                let sets: Vec<String> = pick.iter().enumerate()
+                    .map(|(j, &i)| format!("{} to `v.{}`", AXES[i], AXES[j]))
+                    .collect();
+
+                writeln!(f, "\t/// Sets {}", join(&sets)).unwrap();
+                writeln!(f, "\tpub fn set_{}(&mut self, v: super::Vec{}<T>) {{", name(names, pick), len).unwrap();
+                for (j, &i) in pick.iter().enumerate() {
+                    writeln!(f, "\t\tself.{} = v.{};", AXES[i], AXES[j]).unwrap();
+                }
+                writeln!(f, "\t}}").unwrap();
             }
         }
     }
 
-    write!(f, "}}\n").unwrap();
-}
+    let fields: Vec<String> = AXES[..dim].iter()
+        .map(|a| format!("self.{}", a))
+        .collect();
 
-fn write_vec4_swizzle(path: &Path) {
-    let axes = ['x', 'y', 'z', 'w'];
+    // conversions between dimensions
+    if dim < 4 {
+        writeln!(f, "\t/// Returns new Vec{} with the elements of self and {} set to `{}`", dim + 1, AXES[dim], AXES[dim]).unwrap();
+        writeln!(f, "\tpub fn extend(&self, {}: T) -> super::Vec{}<T> {{", AXES[dim], dim + 1).unwrap();
+        writeln!(f, "\t\tsuper::Vec{}::from({}, {})", dim + 1, fields.join(", "), AXES[dim]).unwrap();
+        writeln!(f, "\t}}").unwrap();
+    }
+    if dim > 2 {
+        writeln!(f, "\t/// Returns new Vec{} with the elements of self, dropping {}", dim - 1, AXES[dim - 1]).unwrap();
+        writeln!(f, "\tpub fn truncate(&self) -> super::Vec{}<T> {{", dim - 1).unwrap();
+        writeln!(f, "\t\tsuper::Vec{}::from({})", dim - 1, fields[..dim - 1].join(", ")).unwrap();
+        writeln!(f, "\t}}").unwrap();
+    }
+
+    writeln!(f, "}}").unwrap();
+}
 
-    let path = path.join("vec4_swizzle.rs");
+/// Writes the tests of the swizzle-like functions, included in the tests
+/// module of the vector type
+fn write_vec_swizzle_tests(path: &Path, dim: usize) {
+    let path = path.join(format!("vec{}_swizzle_tests.rs", dim));
 
     let mut f = File::create(&path).unwrap();
 
-    write!(f, "/// Swizzle-like functions for generic Vec4 (GENERATED)\n").unwrap();
-    write!(f, "impl<T> Vec4<T> where T: Copy {{\n").unwrap();
+    let values: Vec<String> = (0..dim).map(|i| value(i).to_string()).collect();
+    let zeros: Vec<String> = (0..dim).map(|_| String::from("0")).collect();
 
-    // one
-    for x in axes.iter() {
-        write!(f, "\t/// Returns {}\n", x).unwrap();
-        write!(f, "\tpub fn {}(&self) -> T {{\n", x).unwrap();
-        write!(f, "\t\tself.{}\n", x).unwrap();
-        write!(f, "\t}}\n").unwrap();
-    }
+    writeln!(f, "// Swizzle-like function tests for generic Vec{} (GENERATED)", dim).unwrap();
 
-    // two
-    for x in axes.iter() {
-        for y in axes.iter() {
-            write!(f, "\t/// Returns new Vec2 with x set to `{}` and y set to `{}`\n", x, y).unwrap();
-            write!(f, "\tpub fn {}{}(&self) -> super::Vec2<T> {{\n", x, y).unwrap();
-            write!(f, "\t\tsuper::Vec2::from(self.{}, self.{})\n", x, y).unwrap();
-            write!(f, "\t}}\n").unwrap();
+    // every getter returns the picked elements
+    writeln!(f, "\n#[test]").unwrap();
+    writeln!(f, "fn swizzle_getters() {{").unwrap();
+    writeln!(f, "\tlet v = super::super::Vec{}::from({});", dim, values.join(", ")).unwrap();
+    for names in [&AXES[..dim], &COLOURS[..dim]].iter() {
+        for i in 0..dim {
+            writeln!(f, "\tassert_eq!(v.{}(), {});", names[i], value(i)).unwrap();
         }
-    }
-
-    // three
-    for x in axes.iter() {
-        for y in axes.iter() {
-            for z in axes.iter() {
-                write!(f, "\t/// Returns new Vec3 with x set to `{}`, y set to `{}` and z set to `{}`\n", x, y, z).unwrap();
-                write!(f, "\tpub fn {}{}{}(&self) -> super::Vec3<T> {{\n", x, y, z).unwrap();
-                write!(f, "\t\tsuper::Vec3::from(self.{}, self.{}, self.{})\n", x, y, z).unwrap();
-                write!(f, "\t}}\n").unwrap();
+        for len in 2..dim + 1 {
+            for pick in picks(dim, len, true).iter() {
+                let expected: Vec<String> = pick.iter()
+                    .map(|&i| value(i).to_string())
+                    .collect();
+                writeln!(f, "\tassert_eq!(v.{}(), super::super::Vec{}::from({}));", name(names, pick), len, expected.join(", ")).unwrap();
             }
         }
     }
-
-    // four
-    for x in axes.iter() {
-        for y in axes.iter() {
-            for z in axes.iter() {
-                for w in axes.iter() {
-                    write!(f, "\t/// Returns new Vec4 with x set to `{}`, y set to `{}`, z set to `{}` and w set to `{}`\n", x, y, z, w).unwrap();
-                    write!(f, "\tpub fn {}{}{}{}(&self) -> Self {{\n", x, y, z, w).unwrap();
-                    write!(f, "\t\tVec4::from(self.{}, self.{}, self.{}, self.{})\n", x, y, z, w).unwrap();
-                    write!(f, "\t}}\n").unwrap();
-                }
+    writeln!(f, "}}").unwrap();
+
+    // every setter round-trips through the getter of the same name
+    writeln!(f, "\n#[test]").unwrap();
+    writeln!(f, "fn swizzle_setters() {{").unwrap();
+    writeln!(f, "\tlet src = super::super::Vec{}::from({});", dim, values.join(", ")).unwrap();
+    for names in [&AXES[..dim], &COLOURS[..dim]].iter() {
+        for len in 2..dim + 1 {
+            for pick in picks(dim, len, false).iter() {
+                let n = name(names, pick);
+                writeln!(f, "\tlet mut v = super::super::Vec{}::from({});", dim, zeros.join(", ")).unwrap();
+                writeln!(f, "\tv.set_{}(src.{}());", n, n).unwrap();
+                writeln!(f, "\tassert_eq!(v.{}(), src.{}());", n, n).unwrap();
             }
         }
     }
-
-    write!(f, "}}\n").unwrap();
+    writeln!(f, "}}").unwrap();
+
+    // extend and truncate
+    writeln!(f, "\n#[test]").unwrap();
+    writeln!(f, "fn swizzle_conversions() {{").unwrap();
+    writeln!(f, "\tlet v = super::super::Vec{}::from({});", dim, values.join(", ")).unwrap();
+    if dim < 4 {
+        writeln!(f, "\tassert_eq!(v.extend({}), super::super::Vec{}::from({}, {}));", value(dim), dim + 1, values.join(", "), value(dim)).unwrap();
+        writeln!(f, "\tassert_eq!(v.extend(0).{}(), v);", name(&AXES, &(0..dim).collect::<Vec<_>>())).unwrap();
+    }
+    if dim > 2 {
+        writeln!(f, "\tassert_eq!(v.truncate(), super::super::Vec{}::from({}));", dim - 1, values[..dim - 1].join(", ")).unwrap();
+        writeln!(f, "\tassert_eq!(v.truncate().extend({}), v);", value(dim - 1)).unwrap();
+    }
+    writeln!(f, "}}").unwrap();
 }
-
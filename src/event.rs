@@ -0,0 +1,379 @@
+//! Window and gamepad events, independent of the backends producing them
+//!
+//! A [`Window`](../graphics/struct.Window.html) translates what its backend
+//! reports into these types, so nothing outside the `graphics` module needs
+//! to know which backend is in use. They are available without the
+//! `graphics` feature too, for feeding recorded or simulated input to a
+//! headless Engine.
+
+use std::path::PathBuf;
+
+/// Something that happened to a Window or was done to it by the user
+#[derive(Clone,PartialEq,Debug,Serialize,Deserialize)]
+pub enum WindowEvent {
+    /// The user asked to close the Window
+    Closed,
+    /// The Window was resized to this width and height, in pixels
+    Resized(u32, u32),
+    /// The Window gained (true) or lost (false) the keyboard focus
+    Focused(bool),
+    /// A key was pressed, or is repeating while held
+    KeyDown(Key),
+    /// A key was released
+    KeyUp(Key),
+    /// The cursor moved to this position, in pixels from the top left corner
+    /// of the Window
+    MouseMoved(i32, i32),
+    /// A mouse button was pressed or released
+    MouseButton(MouseButton, ButtonState),
+    /// The mouse wheel or touchpad was scrolled
+    Scroll(ScrollDelta),
+    /// A character was typed, after keyboard layout and input methods
+    TextInput(char),
+    /// A file was dropped onto the Window
+    DroppedFile(PathBuf)
+}
+
+/// Whether a button has been pressed or released
+#[derive(Copy,Clone,PartialEq,Eq,Hash,Debug,Serialize,Deserialize)]
+pub enum ButtonState {
+    /// The button went down
+    Pressed,
+    /// The button came back up
+    Released
+}
+
+/// A button on the mouse
+#[derive(Copy,Clone,PartialEq,Eq,Hash,Debug,Serialize,Deserialize)]
+pub enum MouseButton {
+    /// The left, or primary, button
+    Left,
+    /// The right, or secondary, button
+    Right,
+    /// The middle button, often the wheel
+    Middle,
+    /// Any other button, by the backend's number for it
+    Other(u8)
+}
+
+/// How far a scroll went, horizontally and vertically
+///
+/// Positive values scroll right and away from the user.
+#[derive(Copy,Clone,PartialEq,Debug,Serialize,Deserialize)]
+pub enum ScrollDelta {
+    /// Scrolled by this many lines, as by a mouse wheel
+    Lines(f32, f32),
+    /// Scrolled by this many pixels, as by a touchpad
+    Pixels(f32, f32)
+}
+
+/// A key on the keyboard, by its meaning in a US layout
+#[derive(Copy,Clone,PartialEq,Eq,Hash,Debug,Serialize,Deserialize)]
+pub enum Key {
+    /// The 0 key above the letters
+    Key0,
+    /// The 1 key above the letters
+    Key1,
+    /// The 2 key above the letters
+    Key2,
+    /// The 3 key above the letters
+    Key3,
+    /// The 4 key above the letters
+    Key4,
+    /// The 5 key above the letters
+    Key5,
+    /// The 6 key above the letters
+    Key6,
+    /// The 7 key above the letters
+    Key7,
+    /// The 8 key above the letters
+    Key8,
+    /// The 9 key above the letters
+    Key9,
+    /// The A key
+    A,
+    /// The B key
+    B,
+    /// The C key
+    C,
+    /// The D key
+    D,
+    /// The E key
+    E,
+    /// The F key
+    F,
+    /// The G key
+    G,
+    /// The H key
+    H,
+    /// The I key
+    I,
+    /// The J key
+    J,
+    /// The K key
+    K,
+    /// The L key
+    L,
+    /// The M key
+    M,
+    /// The N key
+    N,
+    /// The O key
+    O,
+    /// The P key
+    P,
+    /// The Q key
+    Q,
+    /// The R key
+    R,
+    /// The S key
+    S,
+    /// The T key
+    T,
+    /// The U key
+    U,
+    /// The V key
+    V,
+    /// The W key
+    W,
+    /// The X key
+    X,
+    /// The Y key
+    Y,
+    /// The Z key
+    Z,
+    /// The F1 key
+    F1,
+    /// The F2 key
+    F2,
+    /// The F3 key
+    F3,
+    /// The F4 key
+    F4,
+    /// The F5 key
+    F5,
+    /// The F6 key
+    F6,
+    /// The F7 key
+    F7,
+    /// The F8 key
+    F8,
+    /// The F9 key
+    F9,
+    /// The F10 key
+    F10,
+    /// The F11 key
+    F11,
+    /// The F12 key
+    F12,
+    /// The Escape key
+    Escape,
+    /// The Tab key
+    Tab,
+    /// The Caps Lock key
+    CapsLock,
+    /// The left Shift key
+    LShift,
+    /// The right Shift key
+    RShift,
+    /// The left Control key
+    LControl,
+    /// The right Control key
+    RControl,
+    /// The left Alt key
+    LAlt,
+    /// The right Alt key
+    RAlt,
+    /// The left Windows, Command or Super key
+    LSuper,
+    /// The right Windows, Command or Super key
+    RSuper,
+    /// The Menu key
+    Menu,
+    /// The space bar
+    Space,
+    /// The Enter or Return key
+    Enter,
+    /// The Backspace key
+    Backspace,
+    /// The Insert key
+    Insert,
+    /// The Delete key
+    Delete,
+    /// The Home key
+    Home,
+    /// The End key
+    End,
+    /// The Page Up key
+    PageUp,
+    /// The Page Down key
+    PageDown,
+    /// The left arrow key
+    Left,
+    /// The right arrow key
+    Right,
+    /// The up arrow key
+    Up,
+    /// The down arrow key
+    Down,
+    /// The Print Screen key
+    PrintScreen,
+    /// The Scroll Lock key
+    ScrollLock,
+    /// The Pause key
+    Pause,
+    /// The ` and ~ key
+    Grave,
+    /// The - and _ key
+    Minus,
+    /// The = and + key
+    Equals,
+    /// The [ and { key
+    LBracket,
+    /// The ] and } key
+    RBracket,
+    /// The \ and | key
+    Backslash,
+    /// The ; and : key
+    Semicolon,
+    /// The ' and " key
+    Apostrophe,
+    /// The , and < key
+    Comma,
+    /// The . and > key
+    Period,
+    /// The / and ? key
+    Slash,
+    /// The Num Lock key
+    NumLock,
+    /// The 0 key on the numpad
+    Numpad0,
+    /// The 1 key on the numpad
+    Numpad1,
+    /// The 2 key on the numpad
+    Numpad2,
+    /// The 3 key on the numpad
+    Numpad3,
+    /// The 4 key on the numpad
+    Numpad4,
+    /// The 5 key on the numpad
+    Numpad5,
+    /// The 6 key on the numpad
+    Numpad6,
+    /// The 7 key on the numpad
+    Numpad7,
+    /// The 8 key on the numpad
+    Numpad8,
+    /// The 9 key on the numpad
+    Numpad9,
+    /// The + key on the numpad
+    NumpadAdd,
+    /// The - key on the numpad
+    NumpadSubtract,
+    /// The * key on the numpad
+    NumpadMultiply,
+    /// The / key on the numpad
+    NumpadDivide,
+    /// The . key on the numpad
+    NumpadDecimal,
+    /// The Enter key on the numpad
+    NumpadEnter,
+    /// Any other key, by its scan code
+    Unknown(u32)
+}
+
+/// A button on a gamepad, by its position on a common controller layout
+#[derive(Copy,Clone,PartialEq,Eq,Hash,Debug,Serialize,Deserialize)]
+pub enum GamepadButton {
+    /// The bottom face button (A on Xbox, Cross on PlayStation)
+    South,
+    /// The right face button (B on Xbox, Circle on PlayStation)
+    East,
+    /// The left face button (X on Xbox, Square on PlayStation)
+    West,
+    /// The top face button (Y on Xbox, Triangle on PlayStation)
+    North,
+    /// The left shoulder button
+    LeftBumper,
+    /// The right shoulder button
+    RightBumper,
+    /// The left trigger, when pressed as a button
+    LeftTrigger,
+    /// The right trigger, when pressed as a button
+    RightTrigger,
+    /// The left stick, pressed in
+    LeftStick,
+    /// The right stick, pressed in
+    RightStick,
+    /// The Select, Back or Share button
+    Select,
+    /// The Start, Menu or Options button
+    Start,
+    /// The logo button in the middle
+    Mode,
+    /// Up on the directional pad
+    DPadUp,
+    /// Down on the directional pad
+    DPadDown,
+    /// Left on the directional pad
+    DPadLeft,
+    /// Right on the directional pad
+    DPadRight
+}
+
+/// An analog axis on a gamepad
+///
+/// Sticks go from -1 to 1, with positive values to the right and up.
+/// Triggers go from 0 when released to 1 when fully pressed.
+#[derive(Copy,Clone,PartialEq,Eq,Hash,Debug,Serialize,Deserialize)]
+pub enum GamepadAxis {
+    /// The left stick, horizontally
+    LeftStickX,
+    /// The left stick, vertically
+    LeftStickY,
+    /// The right stick, horizontally
+    RightStickX,
+    /// The right stick, vertically
+    RightStickY,
+    /// The left trigger
+    LeftTrigger,
+    /// The right trigger
+    RightTrigger
+}
+
+/// Something that happened to a gamepad, which is identified by the number
+/// the gamepad backend gave it
+#[derive(Copy,Clone,PartialEq,Debug,Serialize,Deserialize)]
+pub enum GamepadEvent {
+    /// A gamepad was plugged in or turned on
+    Connected(usize),
+    /// A gamepad was unplugged or turned off
+    Disconnected(usize),
+    /// A button was pressed
+    ButtonDown(usize, GamepadButton),
+    /// A button was released
+    ButtonUp(usize, GamepadButton),
+    /// An axis moved to this value
+    Axis(usize, GamepadAxis, f32)
+}
+
+/// Any event the [`Input`](../input/struct.Input.html) state is built from
+#[derive(Clone,PartialEq,Debug,Serialize,Deserialize)]
+pub enum InputEvent {
+    /// An event from the Window
+    Window(WindowEvent),
+    /// An event from a gamepad
+    Gamepad(GamepadEvent)
+}
+
+impl From<WindowEvent> for InputEvent {
+    fn from(event: WindowEvent) -> Self {
+        InputEvent::Window(event)
+    }
+}
+
+impl From<GamepadEvent> for InputEvent {
+    fn from(event: GamepadEvent) -> Self {
+        InputEvent::Gamepad(event)
+    }
+}
@@ -0,0 +1,55 @@
+/// How the cursor behaves over a Window
+#[derive(Copy,Clone,PartialEq,Eq,Hash,Debug)]
+pub enum CursorMode {
+    /// The cursor is shown and moves freely
+    Normal,
+    /// The cursor is invisible while over the Window
+    Hidden,
+    /// The cursor is invisible and kept inside the Window, for mouse look
+    /// controls
+    Grabbed
+}
+
+/// An image for the cursor, from the ones the system provides
+///
+/// Custom cursor images are not supported yet: glutin 0.7, which the Window
+/// is built on, can only show the system's cursors, so
+/// [`Window::set_cursor_image`](struct.Window.html#method.set_cursor_image)
+/// returns an error. A game that needs its own cursor can hide the system
+/// one with [`CursorMode::Hidden`](enum.CursorMode.html) and draw its image
+/// at the mouse position.
+#[derive(Copy,Clone,PartialEq,Eq,Hash,Debug)]
+pub enum CursorIcon {
+    /// The system's usual cursor
+    Default,
+    /// An arrow
+    Arrow,
+    /// A crosshair, for aiming or picking
+    Crosshair,
+    /// A pointing hand, for links and buttons
+    Hand,
+    /// A text cursor, for text fields
+    Text,
+    /// Something is loading and the Window can't be used
+    Wait,
+    /// Something is loading but the Window can still be used
+    Progress,
+    /// Something can be moved in any direction
+    Move,
+    /// What is under the cursor can't be used
+    NotAllowed,
+    /// Something can be grabbed
+    Grab,
+    /// Something is being grabbed
+    Grabbing,
+    /// Something can be resized horizontally
+    ResizeHorizontal,
+    /// Something can be resized vertically
+    ResizeVertical,
+    /// Something can be resized along the diagonal from bottom left to top
+    /// right
+    ResizeDiagonal,
+    /// Something can be resized along the diagonal from top left to bottom
+    /// right
+    ResizeAntiDiagonal
+}
@@ -0,0 +1,59 @@
+use std::error;
+use std::fmt;
+
+/// Reasons a [`Window`](struct.Window.html) could not be created or changed
+#[derive(Clone,PartialEq,Eq,Debug)]
+pub enum WindowError {
+    /// There is no display or windowing system to open a Window on
+    NoDisplay(String),
+    /// No pixel format has the requested depth, stencil and sRGB settings
+    UnsupportedPixelFormat,
+    /// The OpenGL version or features needed are not available
+    UnsupportedOpenGl(String),
+    /// The window or its rendering context could not be created
+    ContextCreation(String),
+    /// The system can't do what was asked of the Window
+    Unsupported(String)
+}
+
+/// Calc methods for WindowError
+impl WindowError {
+    /// Returns true if building again with different settings may succeed,
+    /// which is the case for every error but
+    /// [`NoDisplay`](#variant.NoDisplay)
+    pub fn is_retryable(&self) -> bool {
+        !matches!(*self, WindowError::NoDisplay(_))
+    }
+}
+
+impl fmt::Display for WindowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            WindowError::NoDisplay(ref s) =>
+                write!(f, "no display available: {}", s),
+            WindowError::UnsupportedPixelFormat =>
+                write!(f, "no pixel format matches the requested settings"),
+            WindowError::UnsupportedOpenGl(ref s) =>
+                write!(f, "unsupported OpenGL: {}", s),
+            WindowError::ContextCreation(ref s) =>
+                write!(f, "could not create window: {}", s),
+            WindowError::Unsupported(ref s) =>
+                write!(f, "not supported: {}", s)
+        }
+    }
+}
+
+impl error::Error for WindowError {}
+
+#[cfg(test)]
+mod tests {
+    use super::WindowError;
+
+    #[test]
+    fn retryable() {
+        assert!(!WindowError::NoDisplay(String::from(":0")).is_retryable());
+        assert!(WindowError::UnsupportedPixelFormat.is_retryable());
+        assert_eq!(WindowError::UnsupportedOpenGl(String::from("3.3")).to_string(),
+                   "unsupported OpenGL: 3.3");
+    }
+}
@@ -1,5 +1,11 @@
 //! gfx
 
+mod error;
+pub use self::error::WindowError;
+mod cursor;
+pub use self::cursor::{CursorMode, CursorIcon};
 mod window;
 pub use self::window::Window;
 pub use self::window::WindowBuilder;
+pub use self::window::Fallback;
+pub use self::window::WindowMode;
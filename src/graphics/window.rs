@@ -2,18 +2,285 @@ extern crate glium;
 
 use std::rc::Rc;
 use std::cell::RefCell;
+use std::vec;
+
+use super::error::WindowError;
+use super::cursor::{CursorMode, CursorIcon};
+use event::{WindowEvent, Key, MouseButton, ButtonState, ScrollDelta};
 
 /// Window structure
 ///
 /// Stores the raw handle to the windowing system and the graphics rendering
 /// context
 pub struct Window {
-    facade: glium::backend::glutin_backend::GlutinFacade
+    facade: glium::backend::glutin_backend::GlutinFacade,
+    events: EventQueue,
+    settings: WindowBuilder,
+    cursor_mode: CursorMode,
+    cursor_icon: CursorIcon
+}
+/// Get methods for Window
+impl Window {
+    /// Returns the rendering context, for drawing to the Window
+    pub fn facade(&self) -> &glium::backend::glutin_backend::GlutinFacade {
+        &self.facade
+    }
+    /// Returns true once the user has asked to close the Window
+    pub fn is_closed(&self) -> bool {
+        self.events.closed
+    }
+    /// Returns the size of the Window's drawable area, in pixels
+    pub fn size(&self) -> (u32, u32) {
+        self.glutin_window()
+            .get_inner_size()
+            .unwrap_or((self.settings.width, self.settings.height))
+    }
+    /// Returns the events received by the prior Engine update
+    pub(crate) fn events(&self) -> &[WindowEvent] {
+        &self.events.events
+    }
+    /// Returns the smallest size the user can resize the Window to, if limited
+    pub fn min_size(&self) -> Option<(u32, u32)> {
+        self.settings.min_size
+    }
+    /// Returns the largest size the user can resize the Window to, if limited
+    pub fn max_size(&self) -> Option<(u32, u32)> {
+        self.settings.max_size
+    }
+    /// Returns the Window's title
+    pub fn title(&self) -> &str {
+        &self.settings.title
+    }
+    /// Returns whether the Window is windowed, fullscreen or borderless
+    pub fn mode(&self) -> WindowMode {
+        self.settings.mode
+    }
+    /// Returns how the cursor behaves over the Window
+    ///
+    /// Rebuilding the Window, as [`set_mode`](#method.set_mode) does, keeps
+    /// the cursor mode unless the new Window refuses it, which leaves it
+    /// [`Normal`](enum.CursorMode.html).
+    pub fn cursor_mode(&self) -> CursorMode {
+        self.cursor_mode
+    }
+    /// Returns the cursor's image over the Window
+    pub fn cursor_icon(&self) -> CursorIcon {
+        self.cursor_icon
+    }
+    /// Returns the number of pixels per point on the Window's monitor, which
+    /// is above 1 on high DPI monitors
+    pub fn dpi_factor(&self) -> f32 {
+        self.glutin_window().hidpi_factor()
+    }
+    /// Returns the resolution of the primary monitor, which fullscreen and
+    /// borderless Windows cover
+    pub fn monitor_size(&self) -> (u32, u32) {
+        glium::glutin::get_primary_monitor().get_dimensions()
+    }
+
+    fn glutin_window(&self)
+        -> glium::backend::glutin_backend::WinRef<'_> {
+        self.facade.get_window().expect("Window has no glutin window!")
+    }
+}
+/// Mutate methods for Window
+impl Window {
+    /// Changes the Window's title
+    pub fn set_title(&mut self, title: &str) {
+        self.glutin_window().set_title(title);
+        self.settings.title = String::from(title);
+    }
+    /// Resizes the Window's drawable area, within its minimum and maximum
+    /// size
+    ///
+    /// A fullscreen or borderless Window keeps the monitor's size, and takes
+    /// this size when it goes back to being windowed.
+    pub fn set_size(&mut self, width: u32, height: u32) {
+        let (width, height) = self.settings.clamp_size(width, height);
+        if self.settings.mode == WindowMode::Windowed {
+            self.glutin_window().set_inner_size(width, height);
+        }
+        self.settings.width = width;
+        self.settings.height = height;
+    }
+    /// Switches the Window between windowed, fullscreen and borderless
+    ///
+    /// The rendering context is rebuilt, keeping every buffer, texture and
+    /// other resource made with it.
+    ///
+    /// # Errors
+    /// Returns an error and leaves the Window as it was if it could not be
+    /// rebuilt.
+    pub fn set_mode(&mut self, mode: WindowMode) -> Result<(), WindowError> {
+        if mode == self.settings.mode {
+            return Ok(());
+        }
+
+        let mut settings = self.settings.clone();
+        settings.mode = mode;
+        self.rebuild(settings)
+    }
+    /// Sets the smallest size the user can resize the Window to, or removes
+    /// the limit with None
+    ///
+    /// # Errors
+    /// Returns an error and leaves the Window as it was if it could not be
+    /// rebuilt, which changing the limit requires.
+    pub fn set_min_size(&mut self, size: Option<(u32, u32)>)
+        -> Result<(), WindowError> {
+        let mut settings = self.settings.clone();
+        settings.min_size = size;
+        self.rebuild(settings)
+    }
+    /// Sets the largest size the user can resize the Window to, or removes
+    /// the limit with None
+    ///
+    /// # Errors
+    /// Returns an error and leaves the Window as it was if it could not be
+    /// rebuilt, which changing the limit requires.
+    pub fn set_max_size(&mut self, size: Option<(u32, u32)>)
+        -> Result<(), WindowError> {
+        let mut settings = self.settings.clone();
+        settings.max_size = size;
+        self.rebuild(settings)
+    }
+    /// Shows, hides or grabs the cursor
+    ///
+    /// # Errors
+    /// Returns [`WindowError::Unsupported`](enum.WindowError.html) if the
+    /// system can't put the cursor in this mode.
+    pub fn set_cursor_mode(&mut self, mode: CursorMode)
+        -> Result<(), WindowError> {
+        use graphics::window::glium::glutin::CursorState;
+
+        let state = match mode {
+            CursorMode::Normal => CursorState::Normal,
+            CursorMode::Hidden => CursorState::Hide,
+            CursorMode::Grabbed => CursorState::Grab
+        };
+
+        self.glutin_window()
+            .set_cursor_state(state)
+            .map_err(WindowError::Unsupported)?;
+        self.cursor_mode = mode;
+        Ok(())
+    }
+    /// Changes the cursor's image over the Window
+    ///
+    /// Only the system's cursors are available; see
+    /// [`CursorIcon`](enum.CursorIcon.html).
+    pub fn set_cursor_icon(&mut self, icon: CursorIcon) {
+        self.glutin_window().set_cursor(translate_cursor(icon));
+        self.cursor_icon = icon;
+    }
+    /// Changes the cursor's image over the Window to a custom one, `width`
+    /// by `height` pixels of 8 bit RGBA with its point at `hotspot`
+    ///
+    /// # Errors
+    ///
+    /// Custom cursor images are not implemented yet, so this always returns
+    /// `WindowError::Unsupported`; see [`CursorIcon`](enum.CursorIcon.html)
+    /// for a workaround.
+    pub fn set_cursor_image(&mut self, rgba: &[u8], width: u32, height: u32,
+                            hotspot: (u32, u32)) -> Result<(), WindowError> {
+        // glutin 0.7 can only show the system's cursors
+        let _ = (rgba, width, height, hotspot);
+        Err(WindowError::Unsupported(String::from("custom cursor images")))
+    }
+    /// Moves the cursor to this position, in pixels from the top left corner
+    /// of the Window
+    ///
+    /// # Errors
+    /// Returns [`WindowError::Unsupported`](enum.WindowError.html) if the
+    /// system does not allow moving the cursor.
+    pub fn set_cursor_position(&mut self, x: i32, y: i32)
+        -> Result<(), WindowError> {
+        self.glutin_window()
+            .set_cursor_position(x, y)
+            .map_err(|_| WindowError::Unsupported(
+                String::from("moving the cursor")))
+    }
+
+    /// Returns the events the Window has received since the prior call, oldest
+    /// first
+    ///
+    /// Once the Window belongs to an [`Engine`](../struct.Engine.html), only
+    /// the Engine receives events, and this returns the ones its prior update
+    /// handled. Call it between Engine updates; events left unread are dropped
+    /// by the next update.
+    pub fn poll_events(&mut self) -> vec::Drain<'_, WindowEvent> {
+        let received = self.facade.poll_events().filter_map(translate_event);
+        self.events.poll(received)
+    }
+    /// Hands the receiving of events over to an Engine
+    pub(crate) fn set_engine_owned(&mut self) {
+        self.events.engine_owned = true;
+    }
+    /// Drops any unread events and receives the ones the windowing system has
+    /// sent since
+    pub(crate) fn pump_events(&mut self) {
+        let received = self.facade.poll_events().filter_map(translate_event);
+        self.events.pump(received);
+    }
+
+    /// Rebuilds the Window and its rendering context with `settings`
+    fn rebuild(&mut self, mut settings: WindowBuilder)
+        -> Result<(), WindowError> {
+        use graphics::window::glium::DisplayBuild;
+
+        if self.settings.mode == WindowMode::Windowed {
+            let (width, height) = self.size();
+            settings.width = width;
+            settings.height = height;
+        }
+
+        settings.glutin_builder()
+                .rebuild_glium(&self.facade)
+                .map_err(WindowError::from)?;
+        self.settings = settings;
+        self.settings.place(&self.facade);
+
+        // the new window starts with the default cursor; it has replaced the
+        // old one by now, so a cursor mode it refuses stays Normal rather
+        // than failing the rebuild
+        let (mode, icon) = (self.cursor_mode, self.cursor_icon);
+        self.cursor_mode = CursorMode::Normal;
+        self.cursor_icon = CursorIcon::Default;
+        self.set_cursor_icon(icon);
+        self.set_cursor_mode(mode).ok();
+        Ok(())
+    }
+}
+
+/// Whether a Window has a frame or covers a whole monitor
+#[derive(Copy,Clone,PartialEq,Eq,Hash,Debug)]
+pub enum WindowMode {
+    /// A framed Window the user can move and resize
+    Windowed,
+    /// Exclusive fullscreen on the primary monitor, which may change its video
+    /// mode
+    Fullscreen,
+    /// A Window without a frame, the size of the primary monitor
+    Borderless
+}
+
+/// A setting to give up on when a Window can't be built as requested
+#[derive(Copy,Clone,PartialEq,Eq,Debug)]
+pub enum Fallback {
+    /// Turn sRGB off
+    NoSrgb,
+    /// Lower the depth buffer to at most this many bits
+    Depth(u8),
+    /// Lower the stencil buffer to at most this many bits
+    Stencil(u8),
+    /// Turn vsync off
+    NoVsync
 }
 
 /// WindowBuilder
 ///
 /// Builds a Window
+#[derive(Clone,PartialEq,Debug)]
 pub struct WindowBuilder {
     width: u32,
     height: u32,
@@ -21,7 +288,11 @@ pub struct WindowBuilder {
     vsync: bool,
     depth: u8,
     stencil: u8,
-    srgb: bool
+    srgb: bool,
+    mode: WindowMode,
+    min_size: Option<(u32, u32)>,
+    max_size: Option<(u32, u32)>,
+    fallbacks: Vec<Fallback>
 }
 impl WindowBuilder {
     /// Constructs a new WindowBuilder
@@ -33,7 +304,11 @@ impl WindowBuilder {
             vsync: true,
             depth: 24,
             stencil: 8,
-            srgb: true
+            srgb: true,
+            mode: WindowMode::Windowed,
+            min_size: None,
+            max_size: None,
+            fallbacks: Vec::new()
         }
     }
     /// Requests a size other than default (1024x768)
@@ -67,43 +342,493 @@ impl WindowBuilder {
         self.srgb = srgb;
         self
     }
+    /// Requests a mode other than default (windowed)
+    pub fn with_mode(mut self, mode: WindowMode) -> Self {
+        self.mode = mode;
+        self
+    }
+    /// Requests a smallest size for the user to resize the Window to
+    /// (default: none)
+    pub fn with_min_size(mut self, width: u32, height: u32) -> Self {
+        self.min_size = Some((width, height));
+        self
+    }
+    /// Requests a largest size for the user to resize the Window to
+    /// (default: none)
+    pub fn with_max_size(mut self, width: u32, height: u32) -> Self {
+        self.max_size = Some((width, height));
+        self
+    }
+
+    /// Adds a Fallback to try if the Window can't be built
+    ///
+    /// When building fails the Fallbacks are applied one at a time, in the
+    /// order they were added and on top of each other, until building
+    /// succeeds or they run out. For example, `.with_fallback(Fallback::NoSrgb)
+    /// .with_fallback(Fallback::Depth(16))` tries without sRGB, then also with
+    /// a 16 bit depth buffer.
+    pub fn with_fallback(mut self, fallback: Fallback) -> Self {
+        self.fallbacks.push(fallback);
+        self
+    }
 
     /// Builds the Window and returns it as an Rc<RefCell<Window>>
     ///
     /// The Window isn't returned directly to allow for many systems to be able
     /// to utilize the Window simultaneously.
-    pub fn build(self) -> Rc<RefCell<Window>> {
+    ///
+    /// # Errors
+    /// Returns the error of the last attempt if neither the requested
+    /// settings nor any Fallback worked. Fallbacks are not tried when there
+    /// is no display.
+    pub fn build(self) -> Result<Rc<RefCell<Window>>, WindowError> {
+        let mut error = None;
+
+        for attempt in self.attempts() {
+            match attempt.build_facade() {
+                Ok(facade) => {
+                    attempt.place(&facade);
+                    return Ok(Rc::new(RefCell::new(
+                        Window {
+                            facade,
+                            events: EventQueue::default(),
+                            settings: attempt,
+                            cursor_mode: CursorMode::Normal,
+                            cursor_icon: CursorIcon::Default
+                        }
+                    )));
+                },
+                Err(e) => {
+                    let retry = e.is_retryable();
+                    error = Some(e);
+                    if !retry {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Err(error.unwrap())
+    }
+
+    /// Returns the settings to try building with, in order: as requested,
+    /// then after each Fallback that changes anything
+    fn attempts(&self) -> Vec<WindowBuilder> {
+        let mut attempt = self.clone();
+        attempt.fallbacks.clear();
+
+        let mut attempts = vec![attempt.clone()];
+        for fallback in self.fallbacks.iter() {
+            match *fallback {
+                Fallback::NoSrgb => attempt.srgb = false,
+                Fallback::Depth(bits) => attempt.depth = attempt.depth.min(bits),
+                Fallback::Stencil(bits) =>
+                    attempt.stencil = attempt.stencil.min(bits),
+                Fallback::NoVsync => attempt.vsync = false
+            }
+
+            if attempts.last() != Some(&attempt) {
+                attempts.push(attempt.clone());
+            }
+        }
+        attempts
+    }
+
+    fn build_facade(&self)
+        -> Result<glium::backend::glutin_backend::GlutinFacade, WindowError> {
         use graphics::window::glium::DisplayBuild;
 
+        self.glutin_builder().build_glium().map_err(WindowError::from)
+    }
+
+    fn glutin_builder(&self) -> glium::glutin::WindowBuilder<'static> {
         let mut builder = glium::glutin::WindowBuilder::new();
 
-        builder = builder.with_dimensions(self.width, self.height)
-                         .with_title(self.title)
+        builder = builder.with_title(self.title.clone())
                          .with_depth_buffer(self.depth)
                          .with_stencil_buffer(self.stencil);
 
+        match self.mode {
+            WindowMode::Windowed => {
+                let (width, height) = self.clamp_size(self.width, self.height);
+                builder = builder.with_dimensions(width, height);
+
+                if let Some((width, height)) = self.min_size {
+                    builder = builder.with_min_dimensions(width, height);
+                }
+                if let Some((width, height)) = self.max_size {
+                    builder = builder.with_max_dimensions(width, height);
+                }
+            },
+            WindowMode::Fullscreen => {
+                let monitor = glium::glutin::get_primary_monitor();
+                builder = builder.with_fullscreen(monitor);
+            },
+            WindowMode::Borderless => {
+                let monitor = glium::glutin::get_primary_monitor();
+                let (width, height) = monitor.get_dimensions();
+                builder = builder.with_dimensions(width, height)
+                                 .with_decorations(false);
+            }
+        }
+
         if self.vsync {
             builder = builder.with_vsync();
         }
         if self.srgb {
             builder = builder.with_srgb(Some(true));
         }
+        builder
+    }
+
+    /// Moves a newly built borderless window over the primary monitor
+    fn place(&self, facade: &glium::backend::glutin_backend::GlutinFacade) {
+        if self.mode == WindowMode::Borderless {
+            if let Some(window) = facade.get_window() {
+                window.set_position(0, 0);
+            }
+        }
+    }
+
+    /// Limits a size to the minimum and maximum size, if any
+    fn clamp_size(&self, width: u32, height: u32) -> (u32, u32) {
+        let (mut width, mut height) = (width, height);
+        if let Some((min_width, min_height)) = self.min_size {
+            width = width.max(min_width);
+            height = height.max(min_height);
+        }
+        if let Some((max_width, max_height)) = self.max_size {
+            width = width.min(max_width);
+            height = height.min(max_height);
+        }
+        (width, height)
+    }
+}
+
+impl Default for WindowBuilder {
+    fn default() -> Self {
+        WindowBuilder::new()
+    }
+}
+
+impl From<glium::GliumCreationError<glium::glutin::CreationError>> for WindowError {
+    fn from(e: glium::GliumCreationError<glium::glutin::CreationError>) -> Self {
+        use graphics::window::glium::GliumCreationError;
+        use graphics::window::glium::glutin::CreationError;
+
+        match e {
+            GliumCreationError::BackendCreationError(e) => match e {
+                CreationError::NoBackendAvailable(e) =>
+                    WindowError::NoDisplay(e.to_string()),
+                CreationError::NoAvailablePixelFormat =>
+                    WindowError::UnsupportedPixelFormat,
+                CreationError::OpenGlVersionNotSupported |
+                CreationError::RobustnessNotSupported =>
+                    WindowError::UnsupportedOpenGl(e.to_string()),
+                CreationError::OsError(s) =>
+                    WindowError::ContextCreation(s),
+                e => WindowError::ContextCreation(e.to_string())
+            },
+            GliumCreationError::IncompatibleOpenGl(s) =>
+                WindowError::UnsupportedOpenGl(s)
+        }
+    }
+}
+
+fn translate_cursor(icon: CursorIcon) -> glium::glutin::MouseCursor {
+    use graphics::window::glium::glutin::MouseCursor;
+
+    match icon {
+        CursorIcon::Default => MouseCursor::Default,
+        CursorIcon::Arrow => MouseCursor::Arrow,
+        CursorIcon::Crosshair => MouseCursor::Crosshair,
+        CursorIcon::Hand => MouseCursor::Hand,
+        CursorIcon::Text => MouseCursor::Text,
+        CursorIcon::Wait => MouseCursor::Wait,
+        CursorIcon::Progress => MouseCursor::Progress,
+        CursorIcon::Move => MouseCursor::Move,
+        CursorIcon::NotAllowed => MouseCursor::NotAllowed,
+        CursorIcon::Grab => MouseCursor::Grab,
+        CursorIcon::Grabbing => MouseCursor::Grabbing,
+        CursorIcon::ResizeHorizontal => MouseCursor::EwResize,
+        CursorIcon::ResizeVertical => MouseCursor::NsResize,
+        CursorIcon::ResizeDiagonal => MouseCursor::NeswResize,
+        CursorIcon::ResizeAntiDiagonal => MouseCursor::NwseResize
+    }
+}
+
+/// The events a Window has received, shared by its Engine and
+/// [`poll_events`](struct.Window.html#method.poll_events)
+#[derive(Default)]
+struct EventQueue {
+    events: Vec<WindowEvent>,
+    closed: bool,
+    engine_owned: bool
+}
+
+impl EventQueue {
+    /// Replaces the events with the `received` ones, for an Engine update
+    fn pump<I>(&mut self, received: I)
+        where I: IntoIterator<Item = WindowEvent> {
+        self.events.clear();
+        self.receive(received);
+    }
+    /// Takes the unread events, first receiving new ones unless an Engine
+    /// does
+    fn poll<I>(&mut self, received: I) -> vec::Drain<'_, WindowEvent>
+        where I: IntoIterator<Item = WindowEvent> {
+        if !self.engine_owned {
+            self.receive(received);
+        }
+        self.events.drain(..)
+    }
+    fn receive<I>(&mut self, received: I)
+        where I: IntoIterator<Item = WindowEvent> {
+        for event in received {
+            if event == WindowEvent::Closed {
+                self.closed = true;
+            }
+            self.events.push(event);
+        }
+    }
+}
 
-        let facade = builder.build_glium().unwrap();
+/// Translates a backend event, or returns None for events the engine does
+/// not use
+fn translate_event(event: glium::glutin::Event) -> Option<WindowEvent> {
+    use graphics::window::glium::glutin::{Event, ElementState, MouseScrollDelta};
+    use graphics::window::glium::glutin::MouseButton as Button;
 
-        Rc::new(RefCell::new(
-            Window {
-                facade: facade
+    Some(match event {
+        Event::Closed => WindowEvent::Closed,
+        Event::Resized(w, h) => WindowEvent::Resized(w, h),
+        Event::Focused(focused) => WindowEvent::Focused(focused),
+        Event::KeyboardInput(state, scan_code, code) => {
+            let key = translate_key(code, scan_code);
+            match state {
+                ElementState::Pressed => WindowEvent::KeyDown(key),
+                ElementState::Released => WindowEvent::KeyUp(key)
             }
-        ))
+        },
+        Event::MouseMoved(x, y) => WindowEvent::MouseMoved(x, y),
+        Event::MouseInput(state, button) => {
+            let button = match button {
+                Button::Left => MouseButton::Left,
+                Button::Right => MouseButton::Right,
+                Button::Middle => MouseButton::Middle,
+                Button::Other(n) => MouseButton::Other(n)
+            };
+            let state = match state {
+                ElementState::Pressed => ButtonState::Pressed,
+                ElementState::Released => ButtonState::Released
+            };
+            WindowEvent::MouseButton(button, state)
+        },
+        Event::MouseWheel(delta, _) => WindowEvent::Scroll(match delta {
+            MouseScrollDelta::LineDelta(x, y) => ScrollDelta::Lines(x, y),
+            MouseScrollDelta::PixelDelta(x, y) => ScrollDelta::Pixels(x, y)
+        }),
+        Event::ReceivedCharacter(c) => WindowEvent::TextInput(c),
+        Event::DroppedFile(path) => WindowEvent::DroppedFile(path),
+        _ => return None
+    })
+}
+
+fn translate_key(code: Option<glium::glutin::VirtualKeyCode>, scan_code: u8)
+    -> Key {
+    use graphics::window::glium::glutin::VirtualKeyCode as Code;
+
+    match code {
+        Some(Code::Key0) => Key::Key0,
+        Some(Code::Key1) => Key::Key1,
+        Some(Code::Key2) => Key::Key2,
+        Some(Code::Key3) => Key::Key3,
+        Some(Code::Key4) => Key::Key4,
+        Some(Code::Key5) => Key::Key5,
+        Some(Code::Key6) => Key::Key6,
+        Some(Code::Key7) => Key::Key7,
+        Some(Code::Key8) => Key::Key8,
+        Some(Code::Key9) => Key::Key9,
+        Some(Code::A) => Key::A,
+        Some(Code::B) => Key::B,
+        Some(Code::C) => Key::C,
+        Some(Code::D) => Key::D,
+        Some(Code::E) => Key::E,
+        Some(Code::F) => Key::F,
+        Some(Code::G) => Key::G,
+        Some(Code::H) => Key::H,
+        Some(Code::I) => Key::I,
+        Some(Code::J) => Key::J,
+        Some(Code::K) => Key::K,
+        Some(Code::L) => Key::L,
+        Some(Code::M) => Key::M,
+        Some(Code::N) => Key::N,
+        Some(Code::O) => Key::O,
+        Some(Code::P) => Key::P,
+        Some(Code::Q) => Key::Q,
+        Some(Code::R) => Key::R,
+        Some(Code::S) => Key::S,
+        Some(Code::T) => Key::T,
+        Some(Code::U) => Key::U,
+        Some(Code::V) => Key::V,
+        Some(Code::W) => Key::W,
+        Some(Code::X) => Key::X,
+        Some(Code::Y) => Key::Y,
+        Some(Code::Z) => Key::Z,
+        Some(Code::F1) => Key::F1,
+        Some(Code::F2) => Key::F2,
+        Some(Code::F3) => Key::F3,
+        Some(Code::F4) => Key::F4,
+        Some(Code::F5) => Key::F5,
+        Some(Code::F6) => Key::F6,
+        Some(Code::F7) => Key::F7,
+        Some(Code::F8) => Key::F8,
+        Some(Code::F9) => Key::F9,
+        Some(Code::F10) => Key::F10,
+        Some(Code::F11) => Key::F11,
+        Some(Code::F12) => Key::F12,
+        Some(Code::Escape) => Key::Escape,
+        Some(Code::Tab) => Key::Tab,
+        Some(Code::Capital) => Key::CapsLock,
+        Some(Code::LShift) => Key::LShift,
+        Some(Code::RShift) => Key::RShift,
+        Some(Code::LControl) => Key::LControl,
+        Some(Code::RControl) => Key::RControl,
+        Some(Code::LAlt) => Key::LAlt,
+        Some(Code::LMenu) => Key::LAlt,
+        Some(Code::RAlt) => Key::RAlt,
+        Some(Code::RMenu) => Key::RAlt,
+        Some(Code::LWin) => Key::LSuper,
+        Some(Code::RWin) => Key::RSuper,
+        Some(Code::Apps) => Key::Menu,
+        Some(Code::Space) => Key::Space,
+        Some(Code::Return) => Key::Enter,
+        Some(Code::Back) => Key::Backspace,
+        Some(Code::Insert) => Key::Insert,
+        Some(Code::Delete) => Key::Delete,
+        Some(Code::Home) => Key::Home,
+        Some(Code::End) => Key::End,
+        Some(Code::PageUp) => Key::PageUp,
+        Some(Code::PageDown) => Key::PageDown,
+        Some(Code::Left) => Key::Left,
+        Some(Code::Right) => Key::Right,
+        Some(Code::Up) => Key::Up,
+        Some(Code::Down) => Key::Down,
+        Some(Code::Snapshot) => Key::PrintScreen,
+        Some(Code::Scroll) => Key::ScrollLock,
+        Some(Code::Pause) => Key::Pause,
+        Some(Code::Grave) => Key::Grave,
+        Some(Code::Minus) => Key::Minus,
+        Some(Code::Equals) => Key::Equals,
+        Some(Code::LBracket) => Key::LBracket,
+        Some(Code::RBracket) => Key::RBracket,
+        Some(Code::Backslash) => Key::Backslash,
+        Some(Code::Semicolon) => Key::Semicolon,
+        Some(Code::Apostrophe) => Key::Apostrophe,
+        Some(Code::Comma) => Key::Comma,
+        Some(Code::Period) => Key::Period,
+        Some(Code::Slash) => Key::Slash,
+        Some(Code::Numlock) => Key::NumLock,
+        Some(Code::Numpad0) => Key::Numpad0,
+        Some(Code::Numpad1) => Key::Numpad1,
+        Some(Code::Numpad2) => Key::Numpad2,
+        Some(Code::Numpad3) => Key::Numpad3,
+        Some(Code::Numpad4) => Key::Numpad4,
+        Some(Code::Numpad5) => Key::Numpad5,
+        Some(Code::Numpad6) => Key::Numpad6,
+        Some(Code::Numpad7) => Key::Numpad7,
+        Some(Code::Numpad8) => Key::Numpad8,
+        Some(Code::Numpad9) => Key::Numpad9,
+        Some(Code::Add) => Key::NumpadAdd,
+        Some(Code::Subtract) => Key::NumpadSubtract,
+        Some(Code::Multiply) => Key::NumpadMultiply,
+        Some(Code::Divide) => Key::NumpadDivide,
+        Some(Code::Decimal) => Key::NumpadDecimal,
+        Some(Code::NumpadEnter) => Key::NumpadEnter,
+        _ => Key::Unknown(scan_code as u32)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::{WindowBuilder, Fallback, EventQueue};
+    use event::WindowEvent;
+
     #[test]
+    #[ignore = "needs a display; run with --ignored where there is one"]
     fn open() {
-        #[allow(unused_variables)]
-        let wnd = super::WindowBuilder::new().build();
+        let wnd = WindowBuilder::new()
+            .with_fallback(Fallback::NoSrgb)
+            .build();
+        assert!(wnd.is_ok());
+    }
+    #[test]
+    fn fallbacks() {
+        let builder = WindowBuilder::new()
+            .with_depth(16)
+            .with_fallback(Fallback::NoSrgb)
+            .with_fallback(Fallback::Depth(24))
+            .with_fallback(Fallback::Depth(8))
+            .with_fallback(Fallback::NoVsync);
+
+        let attempts = builder.attempts();
+        let settings: Vec<_> = attempts.iter()
+            .map(|a| (a.srgb, a.depth, a.vsync))
+            .collect();
+
+        // the 24 bit fallback changes nothing, so is not tried
+        assert_eq!(settings, vec![(true, 16, true),
+                                  (false, 16, true),
+                                  (false, 8, true),
+                                  (false, 8, false)]);
+        assert!(attempts.iter().all(|a| a.fallbacks.is_empty()));
+    }
+    #[test]
+    fn size_limits() {
+        let builder = WindowBuilder::new()
+            .with_min_size(640, 480)
+            .with_max_size(1920, 1080);
+
+        assert_eq!(builder.clamp_size(320, 240), (640, 480));
+        assert_eq!(builder.clamp_size(800, 2000), (800, 1080));
+        assert_eq!(WindowBuilder::new().clamp_size(1, 1), (1, 1));
+    }
+    #[test]
+    fn translate() {
+        use super::glium::glutin::{Event, ElementState, VirtualKeyCode};
+        use event::{WindowEvent, Key};
+
+        let down = Event::KeyboardInput(ElementState::Pressed, 30,
+                                        Some(VirtualKeyCode::A));
+        let up = Event::KeyboardInput(ElementState::Released, 98, None);
+
+        assert_eq!(super::translate_event(down), Some(WindowEvent::KeyDown(Key::A)));
+        assert_eq!(super::translate_event(up),
+                   Some(WindowEvent::KeyUp(Key::Unknown(98))));
+        assert_eq!(super::translate_event(Event::Refresh), None);
+    }
+    #[test]
+    fn shared_events() {
+        let mut queue = EventQueue::default();
+
+        // on its own, polling receives the events
+        let polled: Vec<_> = queue.poll(vec![WindowEvent::Focused(true)])
+            .collect();
+        assert_eq!(polled, vec![WindowEvent::Focused(true)]);
+
+        // once an Engine pumps the events, the user polls what it handled
+        queue.engine_owned = true;
+        queue.pump(vec![WindowEvent::Resized(640, 480), WindowEvent::Closed]);
+        assert_eq!(queue.events, vec![WindowEvent::Resized(640, 480),
+                                      WindowEvent::Closed]);
+        assert!(queue.closed);
+        let polled: Vec<_> = queue.poll(vec![WindowEvent::Focused(false)])
+            .collect();
+        assert_eq!(polled, vec![WindowEvent::Resized(640, 480),
+                                WindowEvent::Closed]);
+
+        // and the Engine still receives the events after
+        queue.pump(vec![WindowEvent::Focused(false)]);
+        assert_eq!(queue.events, vec![WindowEvent::Focused(false)]);
     }
 }
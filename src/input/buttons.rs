@@ -0,0 +1,74 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+/// Tracks which buttons of one kind are held, and which changed this frame
+#[derive(Clone,Debug)]
+pub struct Buttons<T: Copy + Eq + Hash> {
+    down: HashSet<T>,
+    pressed: HashSet<T>,
+    released: HashSet<T>
+}
+
+/// Construct methods for Buttons
+impl<T: Copy + Eq + Hash> Buttons<T> {
+    /// Constructs a new Buttons with nothing held
+    pub fn new() -> Self {
+        Buttons {
+            down: HashSet::new(),
+            pressed: HashSet::new(),
+            released: HashSet::new()
+        }
+    }
+}
+
+/// Get methods for Buttons
+impl<T: Copy + Eq + Hash> Buttons<T> {
+    /// Returns true if `button` is held
+    pub fn is_down(&self, button: T) -> bool {
+        self.down.contains(&button)
+    }
+    /// Returns true if `button` was pressed this frame
+    pub fn just_pressed(&self, button: T) -> bool {
+        self.pressed.contains(&button)
+    }
+    /// Returns true if `button` was released this frame
+    pub fn just_released(&self, button: T) -> bool {
+        self.released.contains(&button)
+    }
+    /// Returns every held button
+    pub fn down(&self) -> &HashSet<T> {
+        &self.down
+    }
+    /// Returns every button pressed this frame
+    pub fn pressed(&self) -> &HashSet<T> {
+        &self.pressed
+    }
+}
+
+/// Mutate methods for Buttons
+impl<T: Copy + Eq + Hash> Buttons<T> {
+    /// Forgets what changed during the prior frame
+    pub fn begin_frame(&mut self) {
+        self.pressed.clear();
+        self.released.clear();
+    }
+    /// Holds `button`; repeats of a held button are ignored
+    pub fn press(&mut self, button: T) {
+        if self.down.insert(button) {
+            self.pressed.insert(button);
+        }
+    }
+    /// Lets go of `button`; releasing a button that is not held is ignored
+    pub fn release(&mut self, button: T) {
+        if self.down.remove(&button) {
+            self.released.insert(button);
+        }
+    }
+    /// Releases every held button, as when the focus is lost and their
+    /// release would go unseen
+    pub fn release_all(&mut self) {
+        for button in self.down.drain() {
+            self.released.insert(button);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use event::{GamepadButton, GamepadAxis};
+use super::buttons::Buttons;
+
+/// The state of one connected gamepad
+#[derive(Clone,Debug)]
+pub struct Gamepad {
+    buttons: Buttons<GamepadButton>,
+    axes: HashMap<GamepadAxis, f32>,
+    connected: bool
+}
+
+/// Construct methods for Gamepad
+impl Gamepad {
+    pub(crate) fn new() -> Self {
+        Gamepad {
+            buttons: Buttons::new(),
+            axes: HashMap::new(),
+            connected: true
+        }
+    }
+}
+
+/// Get methods for Gamepad
+impl Gamepad {
+    /// Returns true if `button` is held
+    pub fn is_down(&self, button: GamepadButton) -> bool {
+        self.buttons.is_down(button)
+    }
+    /// Returns true if `button` was pressed this frame
+    pub fn just_pressed(&self, button: GamepadButton) -> bool {
+        self.buttons.just_pressed(button)
+    }
+    /// Returns true if `button` was released this frame
+    pub fn just_released(&self, button: GamepadButton) -> bool {
+        self.buttons.just_released(button)
+    }
+    /// Returns the value of `axis`, or 0 if it has not moved since the
+    /// gamepad connected
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes.get(&axis).cloned().unwrap_or(0.0)
+    }
+    /// Returns false during the frame the gamepad disconnected, when its
+    /// buttons are all released and its axes are at rest
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    pub(crate) fn buttons(&self) -> &Buttons<GamepadButton> {
+        &self.buttons
+    }
+}
+
+/// Mutate methods for Gamepad
+impl Gamepad {
+    pub(crate) fn begin_frame(&mut self) {
+        self.buttons.begin_frame();
+    }
+    pub(crate) fn press(&mut self, button: GamepadButton) {
+        self.buttons.press(button);
+    }
+    pub(crate) fn release(&mut self, button: GamepadButton) {
+        self.buttons.release(button);
+    }
+    pub(crate) fn set_axis(&mut self, axis: GamepadAxis, value: f32) {
+        self.axes.insert(axis, value);
+    }
+    pub(crate) fn connect(&mut self) {
+        self.connected = true;
+    }
+    /// Releases every held button and rests every axis, as their release
+    /// would go unseen once the gamepad is gone
+    pub(crate) fn disconnect(&mut self) {
+        self.buttons.release_all();
+        self.axes.clear();
+        self.connected = false;
+    }
+}
@@ -0,0 +1,608 @@
+extern crate toml;
+
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use event::{Key, MouseButton, GamepadButton, GamepadAxis};
+use super::Input;
+
+/// A key, mouse button or gamepad button that can be bound to an action
+///
+/// A gamepad button counts on every connected gamepad.
+#[derive(Copy,Clone,PartialEq,Eq,Hash,Debug,Serialize,Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Button {
+    /// A key on the keyboard
+    Key(Key),
+    /// A button on the mouse
+    Mouse(MouseButton),
+    /// A button on any gamepad
+    Gamepad(GamepadButton)
+}
+
+/// Construct methods for Button
+impl Button {
+    /// Returns a button pressed this frame, if any, for binding whatever the
+    /// player presses next
+    pub fn capture(input: &Input) -> Option<Button> {
+        if let Some(&key) = input.keys().pressed().iter().next() {
+            return Some(Button::Key(key));
+        }
+        if let Some(&button) = input.mouse_buttons().pressed().iter().next() {
+            return Some(Button::Mouse(button));
+        }
+        input.gamepad_states()
+             .filter_map(|g| g.buttons().pressed().iter().next())
+             .next()
+             .map(|&button| Button::Gamepad(button))
+    }
+}
+
+/// Calc methods for Button
+impl Button {
+    /// Returns true if the button is held
+    pub fn is_down(&self, input: &Input) -> bool {
+        match *self {
+            Button::Key(key) => input.is_down(key),
+            Button::Mouse(button) => input.is_mouse_down(button),
+            Button::Gamepad(button) =>
+                input.gamepad_states().any(|g| g.is_down(button))
+        }
+    }
+    /// Returns true if the button was pressed this frame
+    pub fn just_pressed(&self, input: &Input) -> bool {
+        match *self {
+            Button::Key(key) => input.just_pressed(key),
+            Button::Mouse(button) => input.mouse_just_pressed(button),
+            Button::Gamepad(button) =>
+                input.gamepad_states().any(|g| g.just_pressed(button))
+        }
+    }
+    /// Returns true if the button was released this frame
+    pub fn just_released(&self, input: &Input) -> bool {
+        match *self {
+            Button::Key(key) => input.just_released(key),
+            Button::Mouse(button) => input.mouse_just_released(button),
+            Button::Gamepad(button) =>
+                input.gamepad_states().any(|g| g.just_released(button))
+        }
+    }
+}
+
+impl From<Key> for Button {
+    fn from(key: Key) -> Self {
+        Button::Key(key)
+    }
+}
+
+impl From<MouseButton> for Button {
+    fn from(button: MouseButton) -> Self {
+        Button::Mouse(button)
+    }
+}
+
+impl From<GamepadButton> for Button {
+    fn from(button: GamepadButton) -> Self {
+        Button::Gamepad(button)
+    }
+}
+
+/// A button, pressed while holding some modifier keys
+///
+/// A chord is down while its button and every modifier are held. It is
+/// pressed when its button is pressed with the modifiers already held, so
+/// holding Control and then pressing S triggers Control+S but the other way
+/// around does not.
+#[derive(Clone,PartialEq,Eq,Hash,Debug,Serialize,Deserialize)]
+pub struct Chord {
+    /// The button that triggers the chord
+    pub button: Button,
+    /// The keys to hold while pressing the button
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<Key>
+}
+
+/// Construct methods for Chord
+impl Chord {
+    /// Constructs a new Chord of `button` with no modifiers
+    pub fn new<B: Into<Button>>(button: B) -> Self {
+        Chord {
+            button: button.into(),
+            modifiers: Vec::new()
+        }
+    }
+    /// Adds a key to hold while pressing the button
+    pub fn with_modifier(mut self, key: Key) -> Self {
+        self.modifiers.push(key);
+        self
+    }
+}
+
+/// Calc methods for Chord
+impl Chord {
+    /// Returns true if the button and every modifier are held
+    pub fn is_down(&self, input: &Input) -> bool {
+        self.button.is_down(input) && self.modifiers_down(input)
+    }
+    /// Returns true if the button was pressed this frame while every modifier
+    /// was held
+    pub fn just_pressed(&self, input: &Input) -> bool {
+        self.button.just_pressed(input) && self.modifiers_down(input)
+    }
+    /// Returns true if the button was released this frame while every
+    /// modifier was held, or released along with it
+    pub fn just_released(&self, input: &Input) -> bool {
+        self.button.just_released(input) &&
+        self.modifiers.iter().all(|&key| {
+            input.is_down(key) || input.just_released(key)
+        })
+    }
+
+    fn modifiers_down(&self, input: &Input) -> bool {
+        self.modifiers.iter().all(|&key| input.is_down(key))
+    }
+}
+
+impl<B: Into<Button>> From<B> for Chord {
+    fn from(button: B) -> Self {
+        Chord::new(button)
+    }
+}
+
+/// Something that drives an axis, giving a value from -1 to 1
+#[derive(Clone,PartialEq,Eq,Hash,Debug,Serialize,Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AxisBinding {
+    /// A pair of buttons, giving -1 while `negative` is held, 1 while
+    /// `positive` is held and 0 while both or neither are
+    Buttons {
+        /// The button giving -1
+        negative: Button,
+        /// The button giving 1
+        positive: Button
+    },
+    /// An analog axis on any gamepad, past the axis' dead zone
+    Gamepad(GamepadAxis)
+}
+
+/// Calc methods for AxisBinding
+impl AxisBinding {
+    /// Calculates the binding's value, with `dead_zone` applied to analog
+    /// axes
+    pub fn value(&self, input: &Input, dead_zone: f32) -> f32 {
+        match *self {
+            AxisBinding::Buttons { negative, positive } => {
+                let down = |b: Button| if b.is_down(input) { 1.0 } else { 0.0 };
+                down(positive) - down(negative)
+            },
+            AxisBinding::Gamepad(axis) => {
+                input.gamepad_states()
+                     .map(|g| apply_dead_zone(g.axis(axis), dead_zone))
+                     .fold(0.0, strongest)
+            }
+        }
+    }
+}
+
+/// The bindings of one axis
+#[derive(Clone,PartialEq,Debug,Serialize,Deserialize)]
+struct Axis {
+    #[serde(default = "default_dead_zone")]
+    dead_zone: f32,
+    bindings: Vec<AxisBinding>
+}
+
+fn default_dead_zone() -> f32 {
+    0.15
+}
+
+/// Reasons bindings could not be loaded or saved
+#[derive(Clone,PartialEq,Eq,Debug)]
+pub enum MappingError {
+    /// The file could not be read or written
+    Io(String),
+    /// The file is not valid TOML, or does not describe bindings
+    Parse(String),
+    /// The bindings could not be written as TOML
+    Serialize(String)
+}
+
+impl fmt::Display for MappingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MappingError::Io(ref s) => write!(f, "could not access bindings: {}", s),
+            MappingError::Parse(ref s) => write!(f, "invalid bindings: {}", s),
+            MappingError::Serialize(ref s) =>
+                write!(f, "could not write bindings: {}", s)
+        }
+    }
+}
+
+impl error::Error for MappingError {}
+
+/// Named actions and axes, and the inputs bound to them
+///
+/// Gameplay code asks for actions like "jump" and axes like "move_x" rather
+/// than raw keys, so players and designers can change the bindings without
+/// touching it. An action can have any number of
+/// [`Chord`](struct.Chord.html)s, and is down while any of them is. An axis
+/// takes the value of whichever of its bindings is pushed furthest.
+///
+/// Bindings are saved as TOML:
+///
+/// ```toml
+/// [actions]
+/// jump = [{ button = { key = "Space" } }, { button = { gamepad = "South" } }]
+/// save = [{ button = { key = "S" }, modifiers = ["LControl"] }]
+///
+/// [axes.move_x]
+/// dead_zone = 0.2
+/// bindings = [
+///     { buttons = { negative = { key = "A" }, positive = { key = "D" } } },
+///     { gamepad = "LeftStickX" },
+/// ]
+/// ```
+#[derive(Clone,PartialEq,Debug,Default,Serialize,Deserialize)]
+pub struct InputMap {
+    #[serde(default)]
+    actions: BTreeMap<String, Vec<Chord>>,
+    #[serde(default)]
+    axes: BTreeMap<String, Axis>
+}
+
+/// Construct methods for InputMap
+impl InputMap {
+    /// Constructs a new InputMap with no actions or axes
+    pub fn new() -> Self {
+        InputMap {
+            actions: BTreeMap::new(),
+            axes: BTreeMap::new()
+        }
+    }
+    /// Constructs a new InputMap from bindings in TOML
+    ///
+    /// # Errors
+    /// Returns [`MappingError::Parse`](enum.MappingError.html) if the TOML
+    /// does not describe bindings, or gives an axis a dead zone outside 0 to
+    /// 1.
+    pub fn from_toml(s: &str) -> Result<Self, MappingError> {
+        let map: InputMap = toml::from_str(s)
+            .map_err(|e| MappingError::Parse(e.to_string()))?;
+
+        let outside = map.axes.iter()
+            .find(|&(_, a)| !(0.0..=1.0).contains(&a.dead_zone));
+        if let Some((name, axis)) = outside {
+            return Err(MappingError::Parse(format!(
+                "dead zone of axis \"{}\" is {}, not from 0 to 1",
+                name, axis.dead_zone)));
+        }
+        Ok(map)
+    }
+    /// Constructs a new InputMap from a TOML file of bindings
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, MappingError> {
+        let s = fs::read_to_string(path)
+            .map_err(|e| MappingError::Io(e.to_string()))?;
+        Self::from_toml(&s)
+    }
+}
+
+/// Get methods for InputMap
+impl InputMap {
+    /// Returns the names of the actions, in alphabetical order
+    pub fn actions(&self) -> Vec<&str> {
+        self.actions.keys().map(|s| s.as_str()).collect()
+    }
+    /// Returns the names of the axes, in alphabetical order
+    pub fn axes(&self) -> Vec<&str> {
+        self.axes.keys().map(|s| s.as_str()).collect()
+    }
+    /// Returns the chords bound to `action`
+    pub fn bindings(&self, action: &str) -> &[Chord] {
+        self.actions.get(action).map(|c| c.as_slice()).unwrap_or(&[])
+    }
+    /// Returns the bindings of `axis`
+    pub fn axis_bindings(&self, axis: &str) -> &[AxisBinding] {
+        self.axes.get(axis).map(|a| a.bindings.as_slice()).unwrap_or(&[])
+    }
+    /// Returns the dead zone of `axis`, or `None` if it has no bindings
+    pub fn dead_zone(&self, axis: &str) -> Option<f32> {
+        self.axes.get(axis).map(|a| a.dead_zone)
+    }
+}
+
+/// Calc methods for InputMap
+impl InputMap {
+    /// Returns true if any chord bound to `action` is down
+    pub fn is_down(&self, input: &Input, action: &str) -> bool {
+        self.bindings(action).iter().any(|c| c.is_down(input))
+    }
+    /// Returns true if any chord bound to `action` was pressed this frame
+    pub fn just_pressed(&self, input: &Input, action: &str) -> bool {
+        self.bindings(action).iter().any(|c| c.just_pressed(input))
+    }
+    /// Returns true if any chord bound to `action` was released this frame
+    pub fn just_released(&self, input: &Input, action: &str) -> bool {
+        self.bindings(action).iter().any(|c| c.just_released(input))
+    }
+    /// Calculates the value of `axis` from -1 to 1, or 0 if it has no
+    /// bindings
+    pub fn axis(&self, input: &Input, axis: &str) -> f32 {
+        match self.axes.get(axis) {
+            Some(a) => a.bindings.iter()
+                        .map(|b| b.value(input, a.dead_zone))
+                        .fold(0.0, strongest)
+                        .clamp(-1.0, 1.0),
+            None => 0.0
+        }
+    }
+    /// Returns the bindings as TOML
+    pub fn to_toml(&self) -> Result<String, MappingError> {
+        toml::to_string_pretty(self)
+            .map_err(|e| MappingError::Serialize(e.to_string()))
+    }
+    /// Saves the bindings to a TOML file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), MappingError> {
+        fs::write(path, self.to_toml()?)
+            .map_err(|e| MappingError::Io(e.to_string()))
+    }
+}
+
+/// Mutate methods for InputMap
+impl InputMap {
+    /// Binds a chord, or a lone button, to `action`
+    pub fn bind<C: Into<Chord>>(&mut self, action: &str, chord: C) {
+        let chord = chord.into();
+        let chords = self.actions.entry(String::from(action)).or_default();
+        if !chords.contains(&chord) {
+            chords.push(chord);
+        }
+    }
+    /// Removes a chord from `action`; returns false if it was not bound
+    pub fn unbind<C: Into<Chord>>(&mut self, action: &str, chord: C) -> bool {
+        let chord = chord.into();
+        match self.actions.get_mut(action) {
+            Some(chords) => {
+                let len = chords.len();
+                chords.retain(|c| *c != chord);
+                chords.len() != len
+            },
+            None => false
+        }
+    }
+    /// Replaces the chord `old` of `action` with `new`, keeping its place;
+    /// returns false if `old` was not bound
+    pub fn rebind<C, D>(&mut self, action: &str, old: C, new: D) -> bool
+        where C: Into<Chord>, D: Into<Chord> {
+        let (old, new) = (old.into(), new.into());
+        let chords = match self.actions.get_mut(action) {
+            Some(chords) => chords,
+            None => return false
+        };
+
+        match chords.iter().position(|c| *c == old) {
+            Some(i) => {
+                chords[i] = new;
+                let mut seen = Vec::new();
+                chords.retain(|c| if seen.contains(c) {
+                    false
+                } else {
+                    seen.push(c.clone());
+                    true
+                });
+                true
+            },
+            None => false
+        }
+    }
+    /// Removes `action` and all its chords
+    pub fn remove_action(&mut self, action: &str) {
+        self.actions.remove(action);
+    }
+    /// Adds a binding to `axis`
+    pub fn bind_axis(&mut self, axis: &str, binding: AxisBinding) {
+        let a = self.axis_entry(axis);
+        if !a.bindings.contains(&binding) {
+            a.bindings.push(binding);
+        }
+    }
+    /// Removes a binding from `axis`; returns false if it was not bound
+    pub fn unbind_axis(&mut self, axis: &str, binding: &AxisBinding) -> bool {
+        match self.axes.get_mut(axis) {
+            Some(a) => {
+                let len = a.bindings.len();
+                a.bindings.retain(|b| b != binding);
+                a.bindings.len() != len
+            },
+            None => false
+        }
+    }
+    /// Sets how far, from 0 to 1, the gamepad axes bound to `axis` must move
+    /// before they count (default: 0.15)
+    ///
+    /// Past the dead zone values are rescaled to start from 0 again. A NaN
+    /// dead zone counts as the default.
+    pub fn set_dead_zone(&mut self, axis: &str, dead_zone: f32) {
+        let dead_zone = if dead_zone.is_nan() {
+            default_dead_zone()
+        } else {
+            dead_zone.clamp(0.0, 1.0)
+        };
+        self.axis_entry(axis).dead_zone = dead_zone;
+    }
+    /// Removes `axis` and all its bindings
+    pub fn remove_axis(&mut self, axis: &str) {
+        self.axes.remove(axis);
+    }
+
+    fn axis_entry(&mut self, axis: &str) -> &mut Axis {
+        self.axes.entry(String::from(axis)).or_insert_with(|| Axis {
+            dead_zone: default_dead_zone(),
+            bindings: Vec::new()
+        })
+    }
+}
+
+fn apply_dead_zone(value: f32, dead_zone: f32) -> f32 {
+    let magnitude = value.abs();
+    if magnitude <= dead_zone {
+        0.0
+    } else {
+        value.signum()*((magnitude - dead_zone)/(1.0 - dead_zone)).min(1.0)
+    }
+}
+
+/// Picks whichever value is further from 0
+fn strongest(a: f32, b: f32) -> f32 {
+    if b.abs() > a.abs() { b } else { a }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InputMap, Button, Chord, AxisBinding, MappingError};
+    use input::{Input, frame};
+    use event::{WindowEvent, GamepadEvent, GamepadButton, GamepadAxis, Key,
+                MouseButton, ButtonState};
+
+    #[test]
+    fn actions() {
+        let mut map = InputMap::new();
+        map.bind("jump", Key::Space);
+        map.bind("jump", GamepadButton::South);
+        map.bind("fire", MouseButton::Left);
+        map.bind("save", Chord::new(Key::S).with_modifier(Key::LControl));
+
+        let mut input = Input::new(800, 600);
+        input.handle_gamepad_event(&GamepadEvent::ButtonDown(2, GamepadButton::South));
+        assert!(map.just_pressed(&input, "jump") && map.is_down(&input, "jump"));
+        assert!(!map.is_down(&input, "fire"));
+        assert!(!map.is_down(&input, "unbound"));
+
+        frame(&mut input, vec![
+            WindowEvent::MouseButton(MouseButton::Left, ButtonState::Pressed),
+            WindowEvent::KeyDown(Key::S)
+        ]);
+        assert!(map.just_pressed(&input, "fire"));
+        assert!(!map.is_down(&input, "save"));
+
+        // the modifier must be held before the button is pressed
+        frame(&mut input, vec![WindowEvent::KeyDown(Key::LControl)]);
+        assert!(map.is_down(&input, "save") && !map.just_pressed(&input, "save"));
+        frame(&mut input, vec![WindowEvent::KeyUp(Key::S),
+                               WindowEvent::KeyDown(Key::S)]);
+        assert!(map.just_pressed(&input, "save"));
+
+        frame(&mut input, vec![WindowEvent::KeyUp(Key::S),
+                               WindowEvent::KeyUp(Key::LControl)]);
+        assert!(map.just_released(&input, "save"));
+    }
+    #[test]
+    fn axes() {
+        let mut map = InputMap::new();
+        map.bind_axis("move_x", AxisBinding::Buttons {
+            negative: Button::Key(Key::A),
+            positive: Button::Key(Key::D)
+        });
+        map.bind_axis("move_x", AxisBinding::Gamepad(GamepadAxis::LeftStickX));
+        map.set_dead_zone("move_x", 0.2);
+
+        let mut input = Input::new(800, 600);
+        assert_eq!(map.axis(&input, "move_x"), 0.0);
+        assert_eq!(map.axis(&input, "unbound"), 0.0);
+
+        frame(&mut input, vec![WindowEvent::KeyDown(Key::A)]);
+        assert_eq!(map.axis(&input, "move_x"), -1.0);
+        frame(&mut input, vec![WindowEvent::KeyDown(Key::D)]);
+        assert_eq!(map.axis(&input, "move_x"), 0.0);
+
+        frame(&mut input, vec![WindowEvent::KeyUp(Key::A),
+                               WindowEvent::KeyUp(Key::D)]);
+        let stick = |v| GamepadEvent::Axis(0, GamepadAxis::LeftStickX, v);
+        input.handle_gamepad_event(&stick(0.1));
+        assert_eq!(map.axis(&input, "move_x"), 0.0);
+        input.handle_gamepad_event(&stick(0.6));
+        assert!((map.axis(&input, "move_x") - 0.5).abs() < 0.0001);
+        input.handle_gamepad_event(&stick(-1.0));
+        assert_eq!(map.axis(&input, "move_x"), -1.0);
+    }
+    #[test]
+    fn rebind() {
+        let mut map = InputMap::new();
+        map.bind("jump", Key::Space);
+        map.bind("jump", Key::W);
+
+        let mut input = Input::new(800, 600);
+        assert_eq!(Button::capture(&input), None);
+        frame(&mut input, vec![WindowEvent::KeyDown(Key::J)]);
+        let pressed = Button::capture(&input).unwrap();
+        assert_eq!(pressed, Button::Key(Key::J));
+
+        assert!(map.rebind("jump", Key::Space, pressed));
+        assert!(!map.rebind("jump", Key::Space, Key::K));
+        assert_eq!(map.bindings("jump"), &[Chord::new(Key::J), Chord::new(Key::W)]);
+
+        // rebinding onto another of the action's chords merges them
+        assert!(map.rebind("jump", Key::W, Key::J));
+        assert_eq!(map.bindings("jump"), &[Chord::new(Key::J)]);
+
+        assert!(map.unbind("jump", Key::J));
+        assert!(!map.is_down(&input, "jump"));
+    }
+    #[test]
+    fn toml() {
+        let mut map = InputMap::new();
+        map.bind("jump", Key::Space);
+        map.bind("save", Chord::new(Key::S).with_modifier(Key::LControl));
+        map.bind_axis("move_x", AxisBinding::Buttons {
+            negative: Button::Key(Key::A),
+            positive: Button::Gamepad(GamepadButton::DPadRight)
+        });
+        map.bind_axis("move_x", AxisBinding::Gamepad(GamepadAxis::LeftStickX));
+
+        let s = map.to_toml().unwrap();
+        assert_eq!(InputMap::from_toml(&s), Ok(map));
+
+        let map = InputMap::from_toml(r#"
+            [actions]
+            jump = [{ button = { key = "Space" } }, { button = { mouse = "Right" } }]
+
+            [axes.look]
+            bindings = [{ gamepad = "RightStickX" }]
+        "#).unwrap();
+        assert_eq!(map.actions(), vec!["jump"]);
+        assert_eq!(map.bindings("jump")[1], Chord::new(MouseButton::Right));
+        assert_eq!(map.dead_zone("look"), Some(0.15));
+
+        match InputMap::from_toml("[actions]\njump = [{ button = { key = \"Nope\" } }]") {
+            Err(MappingError::Parse(_)) => {},
+            other => panic!("expected a parse error, got {:?}", other)
+        }
+    }
+    #[test]
+    fn dead_zone_range() {
+        let axis = |dead_zone: &str| format!(
+            "[axes.look]\ndead_zone = {}\nbindings = [{{ gamepad = \"RightStickX\" }}]",
+            dead_zone);
+
+        assert_eq!(InputMap::from_toml(&axis("0.0")).unwrap().dead_zone("look"),
+                   Some(0.0));
+        assert_eq!(InputMap::from_toml(&axis("1.0")).unwrap().dead_zone("look"),
+                   Some(1.0));
+        for &bad in ["-0.5", "1.5", "nan"].iter() {
+            match InputMap::from_toml(&axis(bad)) {
+                Err(MappingError::Parse(_)) => {},
+                other => panic!("expected a parse error for {}, got {:?}", bad, other)
+            }
+        }
+
+        let mut map = InputMap::new();
+        map.set_dead_zone("look", -0.5);
+        assert_eq!(map.dead_zone("look"), Some(0.0));
+        map.set_dead_zone("look", 1.5);
+        assert_eq!(map.dead_zone("look"), Some(1.0));
+        map.set_dead_zone("look", f32::NAN);
+        assert_eq!(map.dead_zone("look"), Some(0.15));
+    }
+}
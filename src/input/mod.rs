@@ -0,0 +1,360 @@
+//! Input module providing keyboard, mouse and gamepad state
+
+use std::collections::HashMap;
+use std::collections::hash_map;
+
+use math::Vec2f;
+use event::{InputEvent, WindowEvent, GamepadEvent, Key, MouseButton,
+            ButtonState, ScrollDelta};
+
+mod buttons;
+use self::buttons::Buttons;
+mod gamepad;
+pub use self::gamepad::Gamepad;
+mod mapping;
+pub use self::mapping::{InputMap, Button, Chord, AxisBinding, MappingError};
+mod record;
+pub use self::record::{Recording, RecordingError};
+
+/// Keyboard, mouse and gamepad state, built up from
+/// [`InputEvent`](../event/enum.InputEvent.html)s
+///
+/// Each frame starts with [`begin_frame`](#method.begin_frame), which forgets
+/// what happened during the prior frame, and then handles that frame's events.
+/// [`Engine::update`](../struct.Engine.html#method.update) does both for you.
+///
+/// Held keys and buttons stay held until they are released. When the Window
+/// loses focus every key and mouse button is released, as their release
+/// events would go to another window.
+#[derive(Clone,Debug)]
+pub struct Input {
+    keys: Buttons<Key>,
+    mouse_buttons: Buttons<MouseButton>,
+    mouse_position: Option<Vec2f>,
+    mouse_delta: Vec2f,
+    scroll: Vec2f,
+    scroll_pixels: Vec2f,
+    text: String,
+    window_size: (u32, u32),
+    focused: bool,
+    gamepads: HashMap<usize, Gamepad>
+}
+
+/// Construct methods for Input
+impl Input {
+    /// Constructs a new Input with nothing held, for a focused window of
+    /// `width` by `height` pixels
+    pub fn new(width: u32, height: u32) -> Self {
+        Input {
+            keys: Buttons::new(),
+            mouse_buttons: Buttons::new(),
+            mouse_position: None,
+            mouse_delta: Vec2f::zero(),
+            scroll: Vec2f::zero(),
+            scroll_pixels: Vec2f::zero(),
+            text: String::new(),
+            window_size: (width, height),
+            focused: true,
+            gamepads: HashMap::new()
+        }
+    }
+}
+
+/// Get methods for Input
+impl Input {
+    /// Returns true if `key` is held
+    pub fn is_down(&self, key: Key) -> bool {
+        self.keys.is_down(key)
+    }
+    /// Returns true if `key` was pressed this frame
+    pub fn just_pressed(&self, key: Key) -> bool {
+        self.keys.just_pressed(key)
+    }
+    /// Returns true if `key` was released this frame
+    pub fn just_released(&self, key: Key) -> bool {
+        self.keys.just_released(key)
+    }
+    /// Returns true if the mouse `button` is held
+    pub fn is_mouse_down(&self, button: MouseButton) -> bool {
+        self.mouse_buttons.is_down(button)
+    }
+    /// Returns true if the mouse `button` was pressed this frame
+    pub fn mouse_just_pressed(&self, button: MouseButton) -> bool {
+        self.mouse_buttons.just_pressed(button)
+    }
+    /// Returns true if the mouse `button` was released this frame
+    pub fn mouse_just_released(&self, button: MouseButton) -> bool {
+        self.mouse_buttons.just_released(button)
+    }
+    /// Returns the cursor position in pixels from the top left corner of the
+    /// window, or `None` before the cursor has moved over it
+    pub fn mouse_position(&self) -> Option<Vec2f> {
+        self.mouse_position
+    }
+    /// Returns the cursor position scaled to the window, from (0, 0) at the
+    /// top left corner to (1, 1) at the bottom right corner
+    pub fn mouse_position_normalized(&self) -> Option<Vec2f> {
+        let (width, height) = self.window_size;
+        self.mouse_position.map(|p| {
+            Vec2f::from(p.x/width.max(1) as f32, p.y/height.max(1) as f32)
+        })
+    }
+    /// Returns how far the cursor moved this frame, in pixels
+    pub fn mouse_delta(&self) -> Vec2f {
+        self.mouse_delta
+    }
+    /// Returns how far the cursor moved this frame, as a fraction of the
+    /// window size
+    pub fn mouse_delta_normalized(&self) -> Vec2f {
+        let (width, height) = self.window_size;
+        Vec2f::from(self.mouse_delta.x/width.max(1) as f32,
+                    self.mouse_delta.y/height.max(1) as f32)
+    }
+    /// Returns how many lines the mouse wheel scrolled this frame
+    pub fn scroll(&self) -> Vec2f {
+        self.scroll
+    }
+    /// Returns how many pixels a touchpad scrolled this frame
+    pub fn scroll_pixels(&self) -> Vec2f {
+        self.scroll_pixels
+    }
+    /// Returns the text typed this frame
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+    /// Returns the size of the window in pixels
+    pub fn window_size(&self) -> (u32, u32) {
+        self.window_size
+    }
+    /// Returns true if the window has the keyboard focus
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+    /// Returns the gamepad numbered `id`, if it is connected
+    ///
+    /// A gamepad that disconnects is kept until the next frame, with its
+    /// buttons released and
+    /// [`is_connected`](struct.Gamepad.html#method.is_connected) false, so
+    /// the release of buttons held at the time is seen.
+    pub fn gamepad(&self, id: usize) -> Option<&Gamepad> {
+        self.gamepads.get(&id)
+    }
+    /// Returns the numbers of the connected gamepads, lowest first
+    pub fn gamepads(&self) -> Vec<usize> {
+        let mut ids: Vec<usize> = self.gamepads.iter()
+            .filter(|&(_, g)| g.is_connected())
+            .map(|(&id, _)| id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    pub(crate) fn keys(&self) -> &Buttons<Key> {
+        &self.keys
+    }
+    pub(crate) fn mouse_buttons(&self) -> &Buttons<MouseButton> {
+        &self.mouse_buttons
+    }
+    pub(crate) fn gamepad_states(&self) -> hash_map::Values<'_, usize, Gamepad> {
+        self.gamepads.values()
+    }
+}
+
+/// Mutate methods for Input
+impl Input {
+    /// Forgets what was pressed, released, moved, scrolled and typed during
+    /// the prior frame; call this once per frame before handling its events
+    pub fn begin_frame(&mut self) {
+        self.keys.begin_frame();
+        self.mouse_buttons.begin_frame();
+        self.mouse_delta = Vec2f::zero();
+        self.scroll = Vec2f::zero();
+        self.scroll_pixels = Vec2f::zero();
+        self.text.clear();
+
+        self.gamepads.retain(|_, g| g.is_connected());
+        for gamepad in self.gamepads.values_mut() {
+            gamepad.begin_frame();
+        }
+    }
+    /// Updates the state with `event`
+    pub fn handle_event(&mut self, event: &InputEvent) {
+        match *event {
+            InputEvent::Window(ref e) => self.handle_window_event(e),
+            InputEvent::Gamepad(ref e) => self.handle_gamepad_event(e)
+        }
+    }
+    /// Updates the state with a window `event`
+    pub fn handle_window_event(&mut self, event: &WindowEvent) {
+        match *event {
+            WindowEvent::Resized(width, height) =>
+                self.window_size = (width, height),
+            WindowEvent::Focused(focused) => {
+                self.focused = focused;
+                if !focused {
+                    self.keys.release_all();
+                    self.mouse_buttons.release_all();
+                }
+            },
+            WindowEvent::KeyDown(key) => self.keys.press(key),
+            WindowEvent::KeyUp(key) => self.keys.release(key),
+            WindowEvent::MouseMoved(x, y) => {
+                let position = Vec2f::from(x as f32, y as f32);
+                if let Some(prior) = self.mouse_position {
+                    self.mouse_delta += position - prior;
+                }
+                self.mouse_position = Some(position);
+            },
+            WindowEvent::MouseButton(button, ButtonState::Pressed) =>
+                self.mouse_buttons.press(button),
+            WindowEvent::MouseButton(button, ButtonState::Released) =>
+                self.mouse_buttons.release(button),
+            WindowEvent::Scroll(ScrollDelta::Lines(x, y)) =>
+                self.scroll += Vec2f::from(x, y),
+            WindowEvent::Scroll(ScrollDelta::Pixels(x, y)) =>
+                self.scroll_pixels += Vec2f::from(x, y),
+            WindowEvent::TextInput(c) => self.text.push(c),
+            WindowEvent::Closed | WindowEvent::DroppedFile(_) => {}
+        }
+    }
+    /// Updates the state with a gamepad `event`
+    ///
+    /// Button and axis events for a gamepad that has not been connected
+    /// connect it.
+    pub fn handle_gamepad_event(&mut self, event: &GamepadEvent) {
+        match *event {
+            GamepadEvent::Connected(id) => {
+                self.gamepad_entry(id);
+            },
+            GamepadEvent::Disconnected(id) => {
+                if let Some(gamepad) = self.gamepads.get_mut(&id) {
+                    gamepad.disconnect();
+                }
+            },
+            GamepadEvent::ButtonDown(id, button) =>
+                self.gamepad_entry(id).press(button),
+            GamepadEvent::ButtonUp(id, button) =>
+                self.gamepad_entry(id).release(button),
+            GamepadEvent::Axis(id, axis, value) =>
+                self.gamepad_entry(id).set_axis(axis, value)
+        }
+    }
+    /// Sets the size of the window in pixels, for normalizing mouse
+    /// coordinates; Resized events set it too
+    pub fn set_window_size(&mut self, width: u32, height: u32) {
+        self.window_size = (width, height);
+    }
+
+    fn gamepad_entry(&mut self, id: usize) -> &mut Gamepad {
+        let gamepad = self.gamepads.entry(id).or_insert_with(Gamepad::new);
+        gamepad.connect();
+        gamepad
+    }
+}
+
+/// Begins a frame of `input` and handles `events`, for tests
+#[cfg(test)]
+pub(crate) fn frame(input: &mut Input, events: Vec<WindowEvent>) {
+    input.begin_frame();
+    for e in events.iter() {
+        input.handle_window_event(e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Input, frame};
+    use event::{WindowEvent, GamepadEvent, GamepadButton, GamepadAxis, Key,
+                MouseButton, ButtonState, ScrollDelta};
+    use math::Vec2f;
+
+    #[test]
+    fn keys() {
+        let mut input = Input::new(800, 600);
+
+        frame(&mut input, vec![WindowEvent::KeyDown(Key::A)]);
+        assert!(input.is_down(Key::A) && input.just_pressed(Key::A));
+
+        // a repeat is not a new press
+        frame(&mut input, vec![WindowEvent::KeyDown(Key::A)]);
+        assert!(input.is_down(Key::A) && !input.just_pressed(Key::A));
+
+        frame(&mut input, vec![WindowEvent::KeyUp(Key::A)]);
+        assert!(!input.is_down(Key::A) && input.just_released(Key::A));
+
+        frame(&mut input, vec![]);
+        assert!(!input.just_released(Key::A));
+
+        // a tap within one frame is both pressed and released
+        frame(&mut input, vec![WindowEvent::KeyDown(Key::B),
+                               WindowEvent::KeyUp(Key::B)]);
+        assert!(input.just_pressed(Key::B) && input.just_released(Key::B));
+        assert!(!input.is_down(Key::B));
+    }
+    #[test]
+    fn focus_lost() {
+        let mut input = Input::new(800, 600);
+        frame(&mut input, vec![
+            WindowEvent::KeyDown(Key::W),
+            WindowEvent::MouseButton(MouseButton::Left, ButtonState::Pressed)
+        ]);
+
+        frame(&mut input, vec![WindowEvent::Focused(false)]);
+        assert!(!input.is_focused());
+        assert!(!input.is_down(Key::W) && input.just_released(Key::W));
+        assert!(input.mouse_just_released(MouseButton::Left));
+    }
+    #[test]
+    fn mouse() {
+        let mut input = Input::new(800, 600);
+        assert_eq!(input.mouse_position(), None);
+
+        // the first position has nothing to move from
+        frame(&mut input, vec![WindowEvent::MouseMoved(100, 150)]);
+        assert_eq!(input.mouse_delta(), Vec2f::zero());
+
+        frame(&mut input, vec![WindowEvent::MouseMoved(200, 200),
+                               WindowEvent::MouseMoved(400, 300),
+                               WindowEvent::Scroll(ScrollDelta::Lines(0.0, 1.0)),
+                               WindowEvent::Scroll(ScrollDelta::Lines(0.0, 2.0))]);
+        assert_eq!(input.mouse_position(), Some(Vec2f::from(400.0, 300.0)));
+        assert_eq!(input.mouse_position_normalized(), Some(Vec2f::from(0.5, 0.5)));
+        assert_eq!(input.mouse_delta(), Vec2f::from(300.0, 150.0));
+        assert_eq!(input.mouse_delta_normalized(), Vec2f::from(0.375, 0.25));
+        assert_eq!(input.scroll(), Vec2f::from(0.0, 3.0));
+
+        frame(&mut input, vec![WindowEvent::Resized(1600, 1200)]);
+        assert_eq!(input.mouse_delta(), Vec2f::zero());
+        assert_eq!(input.scroll(), Vec2f::zero());
+        assert_eq!(input.mouse_position_normalized(), Some(Vec2f::from(0.25, 0.25)));
+    }
+    #[test]
+    fn gamepads() {
+        let mut input = Input::new(800, 600);
+        input.handle_gamepad_event(&GamepadEvent::Connected(1));
+        input.handle_gamepad_event(&GamepadEvent::ButtonDown(0, GamepadButton::South));
+        input.handle_gamepad_event(&GamepadEvent::Axis(0, GamepadAxis::LeftStickX, -0.5));
+        assert_eq!(input.gamepads(), vec![0, 1]);
+
+        let pad = input.gamepad(0).unwrap();
+        assert!(pad.just_pressed(GamepadButton::South));
+        assert_eq!(pad.axis(GamepadAxis::LeftStickX), -0.5);
+        assert_eq!(pad.axis(GamepadAxis::RightTrigger), 0.0);
+
+        input.begin_frame();
+        assert!(input.gamepad(0).unwrap().is_down(GamepadButton::South));
+        assert!(!input.gamepad(0).unwrap().just_pressed(GamepadButton::South));
+
+        // the held button is released for the frame the gamepad goes
+        input.handle_gamepad_event(&GamepadEvent::Disconnected(0));
+        assert_eq!(input.gamepads(), vec![1]);
+        let pad = input.gamepad(0).unwrap();
+        assert!(!pad.is_connected());
+        assert!(!pad.is_down(GamepadButton::South));
+        assert!(pad.just_released(GamepadButton::South));
+        assert_eq!(pad.axis(GamepadAxis::LeftStickX), 0.0);
+
+        input.begin_frame();
+        assert!(input.gamepad(0).is_none());
+    }
+}
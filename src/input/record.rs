@@ -0,0 +1,241 @@
+extern crate rmp_serde;
+
+use std::error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use event::InputEvent;
+
+/// The first bytes of a recording file, the last being the format version
+const MAGIC: &[u8] = b"OXRC\x02";
+
+/// Every input event of a session and the time each frame took, for
+/// replaying the session later
+///
+/// Frames are recorded by an [`Engine`](../struct.Engine.html) after
+/// [`start_recording`](../struct.Engine.html#method.start_recording), and
+/// played back with
+/// [`with_replay`](../struct.Engine.html#method.with_replay). A replay feeds
+/// each frame's events to the Engine's Input and advances its Time by the
+/// recorded frame time, so a game which only depends on those reproduces the
+/// session exactly, headless or not.
+///
+/// The window size when recording started is kept too, since the replayed
+/// Input needs it to normalize mouse coordinates the same way.
+///
+/// Recordings are saved as [MessagePack](https://msgpack.org). Only frames
+/// with events store them, tagged with the frame number.
+#[derive(Clone,PartialEq,Debug,Serialize,Deserialize)]
+pub struct Recording {
+    window_size: (u32, u32),
+    deltas: Vec<f64>,
+    events: Vec<(u64, InputEvent)>
+}
+
+/// Construct methods for Recording
+impl Recording {
+    /// Constructs a new Recording with no frames, of a session starting with
+    /// a window of `width` by `height` pixels
+    pub fn new(width: u32, height: u32) -> Self {
+        Recording {
+            window_size: (width, height),
+            deltas: Vec::new(),
+            events: Vec::new()
+        }
+    }
+    /// Constructs a new Recording from the bytes of a recording file
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RecordingError> {
+        if !bytes.starts_with(MAGIC) {
+            return Err(RecordingError::Format(
+                String::from("not a recording, or of another version")));
+        }
+
+        let recording: Recording = rmp_serde::from_slice(&bytes[MAGIC.len()..])
+            .map_err(|e| RecordingError::Format(e.to_string()))?;
+        if recording.deltas.iter().any(|d| !d.is_finite() || *d < 0.0) {
+            return Err(RecordingError::Format(
+                String::from("frame time that is negative or not finite")));
+        }
+        if recording.events.windows(2).any(|w| w[0].0 > w[1].0) {
+            return Err(RecordingError::Format(
+                String::from("events out of frame order")));
+        }
+        if recording.events.iter().any(|&(f, _)| f >= recording.len() as u64) {
+            return Err(RecordingError::Format(
+                String::from("event after the last frame")));
+        }
+        Ok(recording)
+    }
+    /// Constructs a new Recording from a recording file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, RecordingError> {
+        let bytes = fs::read(path).map_err(|e| RecordingError::Io(e.to_string()))?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+/// Get methods for Recording
+impl Recording {
+    /// Returns the size of the window in pixels when recording started
+    pub fn window_size(&self) -> (u32, u32) {
+        self.window_size
+    }
+    /// Returns the number of frames recorded
+    pub fn len(&self) -> usize {
+        self.deltas.len()
+    }
+    /// Returns true if no frames are recorded
+    pub fn is_empty(&self) -> bool {
+        self.deltas.is_empty()
+    }
+    /// Returns the time `frame` took, in unscaled seconds, and the events
+    /// handled during it, or `None` past the last frame
+    pub fn frame(&self, frame: usize) -> Option<(f64, Vec<InputEvent>)> {
+        let delta = *self.deltas.get(frame)?;
+
+        let frame = frame as u64;
+        let start = self.events.partition_point(|&(f, _)| f < frame);
+        let events = self.events[start..].iter()
+            .take_while(|&&(f, _)| f == frame)
+            .map(|(_, e)| e.clone())
+            .collect();
+        Some((delta, events))
+    }
+}
+
+/// Calc methods for Recording
+impl Recording {
+    /// Calculates the unscaled seconds the recorded frames took altogether
+    pub fn duration(&self) -> f64 {
+        self.deltas.iter().sum()
+    }
+    /// Returns the bytes of a recording file holding the Recording
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend(rmp_serde::to_vec(self)
+            .expect("Recording could not be serialized!"));
+        bytes
+    }
+    /// Saves the Recording to a file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), RecordingError> {
+        fs::write(path, self.to_bytes())
+            .map_err(|e| RecordingError::Io(e.to_string()))
+    }
+}
+
+/// Mutate methods for Recording
+impl Recording {
+    /// Adds a frame that took `delta` unscaled seconds, during which `events`
+    /// were handled
+    pub fn record(&mut self, delta: f64, events: &[InputEvent]) {
+        let frame = self.deltas.len() as u64;
+        self.deltas.push(delta);
+        self.events.extend(events.iter().map(|e| (frame, e.clone())));
+    }
+    /// Forgets every recorded frame, keeping the starting window size
+    pub fn clear(&mut self) {
+        self.deltas.clear();
+        self.events.clear();
+    }
+}
+
+/// Reasons a Recording could not be loaded or saved
+#[derive(Clone,PartialEq,Eq,Debug)]
+pub enum RecordingError {
+    /// The file could not be read or written
+    Io(String),
+    /// The file is not a recording this version can read
+    Format(String)
+}
+
+impl fmt::Display for RecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RecordingError::Io(ref s) => write!(f, "could not access recording: {}", s),
+            RecordingError::Format(ref s) => write!(f, "invalid recording: {}", s)
+        }
+    }
+}
+
+impl error::Error for RecordingError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Recording, RecordingError};
+    use event::{InputEvent, WindowEvent, GamepadEvent, GamepadAxis, Key,
+                ScrollDelta};
+
+    #[test]
+    fn frames() {
+        let mut rec = Recording::new(800, 600);
+        let a = InputEvent::from(WindowEvent::KeyDown(Key::A));
+        let b = InputEvent::from(GamepadEvent::Axis(1, GamepadAxis::LeftStickY, -0.25));
+
+        rec.record(0.25, &[]);
+        rec.record(0.5, &[a.clone(), b.clone()]);
+        rec.record(0.125, &[]);
+
+        assert_eq!(rec.len(), 3);
+        assert_eq!(rec.duration(), 0.875);
+        assert_eq!(rec.frame(0), Some((0.25, vec![])));
+        assert_eq!(rec.frame(1), Some((0.5, vec![a, b])));
+        assert_eq!(rec.frame(2), Some((0.125, vec![])));
+        assert_eq!(rec.frame(3), None);
+    }
+    #[test]
+    fn bytes() {
+        let mut rec = Recording::new(1920, 1080);
+        for i in 0..100 {
+            let events = if i % 10 == 0 {
+                vec![InputEvent::from(WindowEvent::MouseMoved(i, -i)),
+                     InputEvent::from(WindowEvent::Scroll(ScrollDelta::Pixels(0.5, 1.0)))]
+            } else {
+                vec![]
+            };
+            rec.record(1.0/60.0 + i as f64*1e-9, &events);
+        }
+
+        let mut bytes = rec.to_bytes();
+        assert_eq!(Recording::from_bytes(&bytes).map(|r| r.window_size()),
+                   Ok((1920, 1080)));
+        assert_eq!(Recording::from_bytes(&bytes), Ok(rec));
+
+        // recordings of the first version did not keep the window size
+        bytes[4] = 1;
+        assert!(Recording::from_bytes(&bytes).is_err());
+
+        match Recording::from_bytes(b"not a recording") {
+            Err(RecordingError::Format(_)) => {},
+            other => panic!("expected a format error, got {:?}", other)
+        }
+    }
+    #[test]
+    fn malformed() {
+        let a = InputEvent::from(WindowEvent::KeyDown(Key::A));
+        let b = InputEvent::from(WindowEvent::KeyUp(Key::A));
+        let valid = Recording {
+            window_size: (800, 600),
+            deltas: vec![0.25, 0.5],
+            events: vec![(0, a.clone()), (1, b.clone())]
+        };
+        assert_eq!(Recording::from_bytes(&valid.to_bytes()), Ok(valid.clone()));
+
+        let mut bad_deltas = Vec::new();
+        for &delta in [f64::INFINITY, f64::NAN, -0.5].iter() {
+            let mut rec = valid.clone();
+            rec.deltas[1] = delta;
+            bad_deltas.push(rec);
+        }
+        let mut out_of_order = valid.clone();
+        out_of_order.events = vec![(1, b), (0, a.clone())];
+        let mut past_the_end = valid.clone();
+        past_the_end.events.push((2, a));
+
+        for rec in bad_deltas.iter().chain(&[out_of_order, past_the_end]) {
+            match Recording::from_bytes(&rec.to_bytes()) {
+                Err(RecordingError::Format(_)) => {},
+                other => panic!("expected a format error, got {:?}", other)
+            }
+        }
+    }
+}
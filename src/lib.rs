@@ -2,37 +2,363 @@
 #![allow(dead_code)]
 
 //! Oxygine Game Engine
+//!
+//! Windowing and rendering live in the `graphics` module, behind the opt-in
+//! `graphics` feature. Building without it leaves out glium entirely, for
+//! dedicated servers and CI machines with no display or GPU; see
+//! [`Engine::headless`](struct.Engine.html#method.headless).
+//!
+//! The graphics feature uses glium 0.16, whose xml-rs dependency has been
+//! yanked. Cargo only resolves it from a lock file that already lists it, so
+//! copy this crate's Cargo.lock entries when enabling the feature.
+
+#[macro_use]
+extern crate serde;
+
+#[cfg(feature = "graphics")]
+use std::rc::Rc;
+#[cfg(feature = "graphics")]
+use std::cell::RefCell;
 
 pub mod math;
 
 pub mod time;
-use time::Time;
+use time::{Time, Clock, SystemClock, FixedStep};
+
+pub mod event;
+use event::{InputEvent, WindowEvent};
+
+pub mod input;
+use input::{Input, Recording};
 
+#[cfg(feature = "graphics")]
 pub mod graphics;
+#[cfg(feature = "graphics")]
+use graphics::Window;
 
-struct Engine {
-    time: Time
+/// Hooks called by [`Engine::run`](struct.Engine.html#method.run)
+///
+/// Every hook does nothing unless implemented. Each frame the Engine calls
+/// [`fixed_update`](#method.fixed_update) once for every fixed step that has
+/// passed, then [`update`](#method.update) and then
+/// [`render`](#method.render).
+pub trait Game<C: Clock = SystemClock> {
+    /// Called once before the first frame
+    fn init(&mut self, _engine: &mut Engine<C>) {}
+    /// Called once per frame
+    fn update(&mut self, _engine: &mut Engine<C>) {}
+    /// Called once per fixed step, with
+    /// [`FixedStep::step`](time/struct.FixedStep.html#method.step) seconds
+    /// passing each time
+    fn fixed_update(&mut self, _engine: &mut Engine<C>) {}
+    /// Called once per frame after [`update`](#method.update); use
+    /// [`FixedStep::alpha`](time/struct.FixedStep.html#method.alpha) to blend
+    /// between fixed steps
+    fn render(&mut self, _engine: &mut Engine<C>) {}
+    /// Called once after the last frame
+    fn shutdown(&mut self, _engine: &mut Engine<C>) {}
+}
+
+/// Owns the main loop and the systems it drives
+///
+/// [`run`](#method.run) calls the hooks of a [`Game`](trait.Game.html) until
+/// the Window is closed, [`quit`](#method.quit) is called or the tick limit is
+/// reached.
+///
+/// An Engine without a Window runs headless: everything but rendering works
+/// the same, with nothing for the Window to close. Input can still be given
+/// to it with [`push_event`](#method.push_event).
+pub struct Engine<C: Clock = SystemClock> {
+    time: Time<C>,
+    fixed: FixedStep,
+    input: Input,
+    events: Vec<InputEvent>,
+    recording: Option<Recording>,
+    replay: Option<(Recording, usize)>,
+    #[cfg(feature = "graphics")]
+    window: Option<Rc<RefCell<Window>>>,
+    tick_limit: Option<u64>,
+    simulated: bool,
+    quit: bool
 }
 /// Construct methods for Engine
 impl Engine {
     /// Constructs a new Engine
     pub fn new() -> Self {
+        Self::with_clock(SystemClock::new())
+    }
+    /// Constructs a new headless Engine for tests and CI, which runs `ticks`
+    /// fixed steps as fast as it can and stops
+    ///
+    /// Time is simulated (see
+    /// [`with_simulated_time`](#method.with_simulated_time)), so every frame
+    /// runs exactly one fixed step and the results do not depend on the speed
+    /// of the machine.
+    pub fn headless(ticks: u64) -> Self {
+        Self::new()
+            .with_simulated_time()
+            .with_tick_limit(ticks)
+    }
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Engine::new()
+    }
+}
+/// Construct methods for Engine
+impl<C: Clock> Engine<C> {
+    /// Constructs a new Engine with its time read from `clock`
+    pub fn with_clock(clock: C) -> Self {
         Engine {
-            time: Time::new()
+            time: Time::with_clock(clock),
+            fixed: FixedStep::new(60.0),
+            input: Input::new(1024, 768),
+            events: Vec::new(),
+            recording: None,
+            replay: None,
+            #[cfg(feature = "graphics")]
+            window: None,
+            tick_limit: None,
+            simulated: false,
+            quit: false
         }
     }
+    /// Gives the Engine a Window to pump events for and close with
+    ///
+    /// The Window's size is given to the Input, unless a recording is being
+    /// played back, which brings its own.
+    #[cfg(feature = "graphics")]
+    pub fn with_window(mut self, window: Rc<RefCell<Window>>) -> Self {
+        if self.replay.is_none() {
+            let (width, height) = window.borrow().size();
+            self.input.set_window_size(width, height);
+        }
+        window.borrow_mut().set_engine_owned();
+        self.window = Some(window);
+        self
+    }
+    /// Stops [`run`](#method.run) after `ticks` fixed steps; the frame of the
+    /// last step still finishes
+    pub fn with_tick_limit(mut self, ticks: u64) -> Self {
+        self.tick_limit = Some(ticks);
+        self
+    }
+    /// Advances time by one fixed step each update instead of reading the
+    /// clock, so every frame runs exactly one fixed step
+    pub fn with_simulated_time(mut self) -> Self {
+        self.simulated = true;
+        self
+    }
+    /// Plays back `recording` instead of reading the Window's input and the
+    /// clock, stopping [`run`](#method.run) once its last frame is done
+    ///
+    /// The Input starts with the recorded window size. Each update handles
+    /// the next recorded frame's events and advances time by its recorded
+    /// frame time. Events from the Window and
+    /// [`push_event`](#method.push_event) are dropped meanwhile.
+    pub fn with_replay(mut self, recording: Recording) -> Self {
+        let (width, height) = recording.window_size();
+        self.input.set_window_size(width, height);
+        self.replay = Some((recording, 0));
+        self
+    }
+    /// Requests a fixed step rate other than default (60 per second)
+    pub fn with_tick_rate(mut self, tick_rate: f64) -> Self {
+        self.fixed.set_tick_rate(tick_rate);
+        self
+    }
+}
+/// Get methods for Engine
+impl<C: Clock> Engine<C> {
+    /// Returns the Engine's Time
+    pub fn time(&self) -> &Time<C> {
+        &self.time
+    }
+    /// Returns the Engine's Time for changing its scale or pausing it
+    pub fn time_mut(&mut self) -> &mut Time<C> {
+        &mut self.time
+    }
+    /// Returns the fixed step driving
+    /// [`Game::fixed_update`](trait.Game.html#method.fixed_update)
+    pub fn fixed_step(&self) -> &FixedStep {
+        &self.fixed
+    }
+    /// Returns the fixed step for changing its rate
+    pub fn fixed_step_mut(&mut self) -> &mut FixedStep {
+        &mut self.fixed
+    }
+    /// Returns the keyboard, mouse and gamepad state for this frame
+    pub fn input(&self) -> &Input {
+        &self.input
+    }
+    /// Returns the Engine's Window, if it has one
+    #[cfg(feature = "graphics")]
+    pub fn window(&self) -> Option<&Rc<RefCell<Window>>> {
+        self.window.as_ref()
+    }
+    /// Returns true if the Engine has no Window
+    pub fn is_headless(&self) -> bool {
+        #[cfg(feature = "graphics")]
+        {
+            self.window.is_none()
+        }
+        #[cfg(not(feature = "graphics"))]
+        {
+            true
+        }
+    }
+    /// Returns the number of fixed steps [`run`](#method.run) stops after, if
+    /// limited
+    pub fn tick_limit(&self) -> Option<u64> {
+        self.tick_limit
+    }
+    /// Returns true if frames are being recorded
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+    /// Returns the frames recorded so far, if recording
+    pub fn recording(&self) -> Option<&Recording> {
+        self.recording.as_ref()
+    }
+    /// Returns true if a recording is being played back
+    pub fn is_replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+    /// Returns true if the main loop has been asked to stop
+    pub fn is_quitting(&self) -> bool {
+        self.quit
+    }
 }
 /// Mutate methods for Engine
-impl Engine {
+impl<C: Clock> Engine<C> {
     /// Updates the Engine structure; call this once per main loop for correct
     /// functionality!
+    ///
+    /// [`run`](#method.run) calls this for you. It starts a new frame of
+    /// [`Input`](input/struct.Input.html), from the Window's events and then
+    /// the ones given to [`push_event`](#method.push_event).
     pub fn update(&mut self) {
-        self.time.update();
+        self.input.begin_frame();
+        let mut events = Vec::new();
+
+        #[cfg(feature = "graphics")]
+        {
+            if let Some(ref window) = self.window {
+                let mut window = window.borrow_mut();
+                window.pump_events();
+                events.extend(window.events().iter()
+                                    .cloned()
+                                    .map(InputEvent::Window));
+                if window.is_closed() {
+                    self.quit = true;
+                }
+            }
+        }
+        events.append(&mut self.events);
+
+        let mut replayed = None;
+        if let Some((ref recording, ref mut frame)) = self.replay {
+            events.clear();
+            match recording.frame(*frame) {
+                Some((delta, recorded)) => {
+                    events = recorded;
+                    replayed = Some(delta);
+                    *frame += 1;
+                },
+                None => self.quit = true
+            }
+        }
+
+        for event in events.iter() {
+            if *event == InputEvent::Window(WindowEvent::Closed) {
+                self.quit = true;
+            }
+            self.input.handle_event(event);
+        }
+
+        if self.replay.is_some() {
+            self.time.advance(replayed.unwrap_or(0.0));
+        } else if self.simulated {
+            let step = self.fixed.step();
+            self.time.advance(step);
+        } else {
+            self.time.update();
+        }
+
+        if let Some(ref mut recording) = self.recording {
+            recording.record(self.time.unscaled_delta(), &events);
+        }
+    }
+    /// Queues an input event for the next update, as if it came from the
+    /// Window
+    ///
+    /// This feeds a gamepad backend's events to the Engine, and lets a
+    /// headless Engine be given input. A queued
+    /// [`Closed`](event/enum.WindowEvent.html#variant.Closed) event stops the
+    /// main loop like closing the Window would.
+    pub fn push_event<E: Into<InputEvent>>(&mut self, event: E) {
+        self.events.push(event.into());
+    }
+    /// Starts recording every frame's input events and frame time, along
+    /// with the current window size, dropping any earlier recording
+    pub fn start_recording(&mut self) {
+        let (width, height) = self.input.window_size();
+        self.recording = Some(Recording::new(width, height));
+    }
+    /// Stops recording and returns the frames recorded, if recording
+    pub fn stop_recording(&mut self) -> Option<Recording> {
+        self.recording.take()
+    }
+    /// Asks the main loop to stop after the current frame
+    pub fn quit(&mut self) {
+        self.quit = true;
+    }
+    /// Runs the main loop, calling the hooks of `game`, until the Window is
+    /// closed, [`quit`](#method.quit) is called or the tick limit is reached
+    pub fn run<G: Game<C>>(&mut self, game: &mut G) {
+        self.quit = false;
+        let mut ticks = 0;
+        game.init(self);
+
+        if self.tick_limit == Some(0) {
+            self.quit = true;
+        }
+
+        while !self.quit {
+            self.update();
+            if self.quit {
+                break;
+            }
+
+            let mut steps = self.fixed.update(&self.time) as u64;
+            if let Some(limit) = self.tick_limit {
+                steps = steps.min(limit - ticks);
+                if ticks + steps == limit {
+                    self.quit = true;
+                }
+            }
+
+            for _ in 0..steps {
+                game.fixed_update(self);
+            }
+            ticks += steps;
+            game.update(self);
+            game.render(self);
+        }
+
+        game.shutdown(self);
     }
 }
 
 #[cfg(test)]
 mod engine_tests {
+    use super::{Engine, Game};
+    use time::ManualClock;
+    use event::{WindowEvent, GamepadEvent, Key};
+    use input::Recording;
+    use math::Vec2f;
+
     #[test]
     fn construct() {
         #[allow(unused_variables)]
@@ -40,12 +366,188 @@ mod engine_tests {
     }
     #[test]
     fn update() {
-        let mut eng = ::Engine::new();
+        let clock = ::time::ManualClock::new();
+        let mut eng = ::Engine::with_clock(clock.clone());
 
         let a = eng.time.elapsed();
+        clock.advance_secs(0.5);
         eng.update();
         let b = eng.time.elapsed();
 
         assert!(a < b);
+        assert_eq!(eng.time.delta(), 0.5);
+    }
+
+    #[derive(Default)]
+    struct Counter {
+        calls: Vec<&'static str>,
+        frames: u32
+    }
+    impl Game<ManualClock> for Counter {
+        fn init(&mut self, _engine: &mut Engine<ManualClock>) {
+            self.calls.push("init");
+        }
+        fn update(&mut self, engine: &mut Engine<ManualClock>) {
+            self.calls.push("update");
+            self.frames += 1;
+            if self.frames == 3 {
+                engine.quit();
+            }
+            engine.time().clock().advance_secs(0.25);
+        }
+        fn fixed_update(&mut self, _engine: &mut Engine<ManualClock>) {
+            self.calls.push("fixed_update");
+        }
+        fn render(&mut self, _engine: &mut Engine<ManualClock>) {
+            self.calls.push("render");
+        }
+        fn shutdown(&mut self, _engine: &mut Engine<ManualClock>) {
+            self.calls.push("shutdown");
+        }
+    }
+
+    #[test]
+    fn run() {
+        let mut eng = Engine::with_clock(ManualClock::new()).with_tick_rate(4.0);
+        let mut game = Counter::default();
+
+        eng.run(&mut game);
+
+        assert_eq!(game.calls, vec![
+            "init",
+            "update", "render",
+            "fixed_update", "update", "render",
+            "fixed_update", "update", "render",
+            "shutdown"
+        ]);
+        assert_eq!(eng.fixed_step().ticks(), 2);
+        assert!(eng.is_quitting());
+    }
+    #[test]
+    fn headless() {
+        let mut eng = Engine::headless(5).with_tick_rate(4.0);
+        let mut game = Ticks::default();
+        assert!(eng.is_headless());
+
+        eng.run(&mut game);
+
+        // one fixed step per frame, at the simulated rate
+        assert_eq!((game.fixed, game.frames), (5, 5));
+        assert_eq!(eng.time().elapsed(), 1.25);
+        assert_eq!(eng.fixed_step().ticks(), 5);
+    }
+    #[test]
+    fn tick_limit() {
+        let clock = ManualClock::new();
+        let mut eng = Engine::with_clock(clock.clone())
+            .with_tick_rate(4.0)
+            .with_tick_limit(3);
+        let mut game = Ticks::default();
+
+        // a long first frame would run 5 steps, but only 3 are allowed
+        clock.advance_secs(2.0);
+        eng.run(&mut game);
+        assert_eq!((game.fixed, game.frames), (3, 1));
+
+        let mut eng = Engine::headless(0);
+        let mut game = Ticks::default();
+        eng.run(&mut game);
+        assert_eq!((game.fixed, game.frames), (0, 0));
+    }
+
+    #[test]
+    fn input() {
+        let mut eng = Engine::headless(10);
+        eng.push_event(WindowEvent::KeyDown(Key::Space));
+        eng.push_event(GamepadEvent::Connected(0));
+        eng.update();
+        assert!(eng.input().just_pressed(Key::Space));
+        assert!(eng.input().gamepad(0).is_some());
+
+        eng.update();
+        assert!(eng.input().is_down(Key::Space));
+        assert!(!eng.input().just_pressed(Key::Space));
+
+        eng.push_event(WindowEvent::Closed);
+        let mut game = Ticks::default();
+        eng.run(&mut game);
+        assert_eq!(game.frames, 0);
+    }
+
+    /// The ticks, elapsed time, Space key and mouse position (in pixels and
+    /// normalized) one frame saw
+    type Seen = (u64, f64, bool, Option<Vec2f>, Option<Vec2f>);
+
+    /// Keeps what each frame saw, and when `script` is set plays the user by
+    /// pushing input and moving the clock
+    #[derive(Default)]
+    struct Session {
+        script: bool,
+        frames: Vec<Seen>
+    }
+    impl Game<ManualClock> for Session {
+        fn update(&mut self, engine: &mut Engine<ManualClock>) {
+            let frame = self.frames.len() as i32;
+            self.frames.push((engine.fixed_step().ticks(),
+                              engine.time().elapsed(),
+                              engine.input().is_down(Key::Space),
+                              engine.input().mouse_position(),
+                              engine.input().mouse_position_normalized()));
+
+            if self.script {
+                match frame {
+                    1 => engine.push_event(WindowEvent::KeyDown(Key::Space)),
+                    3 => engine.push_event(WindowEvent::KeyUp(Key::Space)),
+                    5 => engine.quit(),
+                    _ => {}
+                }
+                engine.push_event(WindowEvent::MouseMoved(frame*10, frame));
+                engine.time().clock().advance_secs(0.01*(frame + 1) as f64);
+            }
+        }
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let mut eng = Engine::with_clock(ManualClock::new()).with_tick_rate(30.0);
+        let mut game = Session { script: true, frames: Vec::new() };
+
+        // the replay starts with the window size the recording started with
+        eng.push_event(WindowEvent::Resized(640, 480));
+        eng.update();
+        eng.start_recording();
+        eng.run(&mut game);
+
+        let recording = eng.stop_recording().unwrap();
+        assert_eq!(recording.len(), 6);
+        assert_eq!(recording.window_size(), (640, 480));
+        let recording = Recording::from_bytes(&recording.to_bytes()).unwrap();
+
+        // the clock is never read while replaying
+        let mut replay = Engine::with_clock(ManualClock::new())
+            .with_tick_rate(30.0)
+            .with_replay(recording);
+        let mut replayed = Session::default();
+        replay.run(&mut replayed);
+
+        assert!(replay.is_replaying());
+        assert_eq!(replay.input().window_size(), (640, 480));
+        assert_eq!(replayed.frames, game.frames);
+        assert_eq!(replay.time().elapsed(), eng.time().elapsed());
+        assert_eq!(replay.fixed_step().ticks(), eng.fixed_step().ticks());
+    }
+
+    #[derive(Default)]
+    struct Ticks {
+        fixed: u32,
+        frames: u32
+    }
+    impl<C: ::time::Clock> Game<C> for Ticks {
+        fn update(&mut self, _engine: &mut Engine<C>) {
+            self.frames += 1;
+        }
+        fn fixed_update(&mut self, _engine: &mut Engine<C>) {
+            self.fixed += 1;
+        }
     }
 }
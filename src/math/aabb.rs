@@ -0,0 +1,401 @@
+use std::f32;
+
+use super::vec2::Vec2;
+use super::vec3::Vec3;
+use super::mat3::Mat3;
+use super::mat4::Mat4;
+
+/// A 2D axis-aligned bounding box
+///
+/// An Aabb2 with any `min` element greater than the matching `max` element is
+/// empty; [`empty`](#method.empty) returns one that any point or box can be
+/// added to.
+#[derive(Copy,Clone,PartialEq,Debug)]
+pub struct Aabb2 {
+    /// The smallest corner of the box
+    pub min: Vec2<f32>,
+    /// The largest corner of the box
+    pub max: Vec2<f32>
+}
+
+/// A 3D axis-aligned bounding box
+///
+/// An Aabb3 with any `min` element greater than the matching `max` element is
+/// empty; [`empty`](#method.empty) returns one that any point or box can be
+/// added to.
+#[derive(Copy,Clone,PartialEq,Debug)]
+pub struct Aabb3 {
+    /// The smallest corner of the box
+    pub min: Vec3<f32>,
+    /// The largest corner of the box
+    pub max: Vec3<f32>
+}
+
+/// Construct methods for Aabb2
+impl Aabb2 {
+    /// Constructs a new Aabb2 spanning the corners `a` and `b`, in any order
+    pub fn new(a: Vec2<f32>, b: Vec2<f32>) -> Self {
+        Aabb2 {
+            min: a.min(&b),
+            max: a.max(&b)
+        }
+    }
+    /// Constructs a new empty Aabb2 that contains nothing
+    pub fn empty() -> Self {
+        Aabb2 {
+            min: Vec2::from(f32::INFINITY, f32::INFINITY),
+            max: Vec2::from(f32::NEG_INFINITY, f32::NEG_INFINITY)
+        }
+    }
+    /// Constructs a new Aabb2 from `center` and half of its size, `extents`
+    pub fn from_center(center: Vec2<f32>, extents: Vec2<f32>) -> Self {
+        let extents = extents.abs();
+        Aabb2 {
+            min: center - extents,
+            max: center + extents
+        }
+    }
+    /// Constructs the smallest Aabb2 containing every point in `points`
+    ///
+    /// Returns an empty Aabb2 if there are no points.
+    pub fn from_points(points: &[Vec2<f32>]) -> Self {
+        let mut b = Self::empty();
+        for p in points {
+            b.expand(*p);
+        }
+        b
+    }
+}
+
+/// Get methods for Aabb2
+impl Aabb2 {
+    /// Returns true if the Aabb2 contains nothing
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y
+    }
+    /// Returns the point in the middle of the Aabb2
+    pub fn center(&self) -> Vec2<f32> {
+        (self.min + self.max)*0.5
+    }
+    /// Returns the width and height of the Aabb2
+    pub fn size(&self) -> Vec2<f32> {
+        self.max - self.min
+    }
+    /// Returns half the size of the Aabb2
+    pub fn extents(&self) -> Vec2<f32> {
+        self.size()*0.5
+    }
+}
+
+/// Calc methods for Aabb2
+impl Aabb2 {
+    /// Returns true if `p` is inside or on the edge of the Aabb2
+    pub fn contains_point(&self, p: Vec2<f32>) -> bool {
+        p.x >= self.min.x && p.x <= self.max.x &&
+        p.y >= self.min.y && p.y <= self.max.y
+    }
+    /// Returns true if all of `b` is inside the Aabb2
+    ///
+    /// An empty `b` is contained by any Aabb2.
+    pub fn contains(&self, b: &Self) -> bool {
+        b.is_empty() ||
+        (self.contains_point(b.min) && self.contains_point(b.max))
+    }
+    /// Returns true if the Aabb2 and `b` overlap or touch
+    pub fn intersects(&self, b: &Self) -> bool {
+        self.min.x <= b.max.x && self.max.x >= b.min.x &&
+        self.min.y <= b.max.y && self.max.y >= b.min.y
+    }
+    /// Returns the smallest Aabb2 containing both the Aabb2 and `b`
+    pub fn union(&self, b: &Self) -> Self {
+        Aabb2 {
+            min: self.min.min(&b.min),
+            max: self.max.max(&b.max)
+        }
+    }
+    /// Returns the overlapping part of the Aabb2 and `b`, or `None` if they
+    /// do not overlap
+    pub fn intersection(&self, b: &Self) -> Option<Self> {
+        let r = Aabb2 {
+            min: self.min.max(&b.min),
+            max: self.max.min(&b.max)
+        };
+
+        if r.is_empty() { None } else { Some(r) }
+    }
+    /// Returns the point inside the Aabb2 closest to `p`
+    pub fn closest_point(&self, p: Vec2<f32>) -> Vec2<f32> {
+        p.clamp(&self.min, &self.max)
+    }
+    /// Returns a new Aabb2 containing the Aabb2 after transformation by the
+    /// 2D affine Mat3 `m`
+    ///
+    /// The result is conservative: it contains every transformed point but
+    /// may be larger than the transformed box itself.
+    pub fn transform(&self, m: &Mat3) -> Self {
+        if self.is_empty() {
+            return *self;
+        }
+
+        let a = m.as_array();
+        let c = m.transform_point(self.center());
+        let e = self.extents();
+
+        let e = Vec2::from(
+            a[0].abs()*e.x + a[3].abs()*e.y,
+            a[1].abs()*e.x + a[4].abs()*e.y
+        );
+
+        Aabb2::from_center(c, e)
+    }
+}
+
+/// Mutate methods for Aabb2
+impl Aabb2 {
+    /// Grows the Aabb2 to contain `p`
+    pub fn expand(&mut self, p: Vec2<f32>) {
+        self.min = self.min.min(&p);
+        self.max = self.max.max(&p);
+    }
+    /// Grows the Aabb2 by `amount` on every side
+    pub fn inflate(&mut self, amount: f32) {
+        self.min -= amount;
+        self.max += amount;
+    }
+}
+
+/// Construct methods for Aabb3
+impl Aabb3 {
+    /// Constructs a new Aabb3 spanning the corners `a` and `b`, in any order
+    pub fn new(a: Vec3<f32>, b: Vec3<f32>) -> Self {
+        Aabb3 {
+            min: a.min(&b),
+            max: a.max(&b)
+        }
+    }
+    /// Constructs a new empty Aabb3 that contains nothing
+    pub fn empty() -> Self {
+        Aabb3 {
+            min: Vec3::from(f32::INFINITY, f32::INFINITY, f32::INFINITY),
+            max: Vec3::from(f32::NEG_INFINITY, f32::NEG_INFINITY,
+                            f32::NEG_INFINITY)
+        }
+    }
+    /// Constructs a new Aabb3 from `center` and half of its size, `extents`
+    pub fn from_center(center: Vec3<f32>, extents: Vec3<f32>) -> Self {
+        let extents = extents.abs();
+        Aabb3 {
+            min: center - extents,
+            max: center + extents
+        }
+    }
+    /// Constructs the smallest Aabb3 containing every point in `points`
+    ///
+    /// Returns an empty Aabb3 if there are no points.
+    pub fn from_points(points: &[Vec3<f32>]) -> Self {
+        let mut b = Self::empty();
+        for p in points {
+            b.expand(*p);
+        }
+        b
+    }
+}
+
+/// Get methods for Aabb3
+impl Aabb3 {
+    /// Returns true if the Aabb3 contains nothing
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y ||
+        self.min.z > self.max.z
+    }
+    /// Returns the point in the middle of the Aabb3
+    pub fn center(&self) -> Vec3<f32> {
+        (self.min + self.max)*0.5
+    }
+    /// Returns the width, height and depth of the Aabb3
+    pub fn size(&self) -> Vec3<f32> {
+        self.max - self.min
+    }
+    /// Returns half the size of the Aabb3
+    pub fn extents(&self) -> Vec3<f32> {
+        self.size()*0.5
+    }
+    /// Returns the 8 corners of the Aabb3
+    pub fn corners(&self) -> [Vec3<f32>; 8] {
+        let (a, b) = (self.min, self.max);
+        [
+            Vec3::from(a.x, a.y, a.z), Vec3::from(b.x, a.y, a.z),
+            Vec3::from(a.x, b.y, a.z), Vec3::from(b.x, b.y, a.z),
+            Vec3::from(a.x, a.y, b.z), Vec3::from(b.x, a.y, b.z),
+            Vec3::from(a.x, b.y, b.z), Vec3::from(b.x, b.y, b.z)
+        ]
+    }
+}
+
+/// Calc methods for Aabb3
+impl Aabb3 {
+    /// Returns true if `p` is inside or on the surface of the Aabb3
+    pub fn contains_point(&self, p: Vec3<f32>) -> bool {
+        p.x >= self.min.x && p.x <= self.max.x &&
+        p.y >= self.min.y && p.y <= self.max.y &&
+        p.z >= self.min.z && p.z <= self.max.z
+    }
+    /// Returns true if all of `b` is inside the Aabb3
+    ///
+    /// An empty `b` is contained by any Aabb3.
+    pub fn contains(&self, b: &Self) -> bool {
+        b.is_empty() ||
+        (self.contains_point(b.min) && self.contains_point(b.max))
+    }
+    /// Returns true if the Aabb3 and `b` overlap or touch
+    pub fn intersects(&self, b: &Self) -> bool {
+        self.min.x <= b.max.x && self.max.x >= b.min.x &&
+        self.min.y <= b.max.y && self.max.y >= b.min.y &&
+        self.min.z <= b.max.z && self.max.z >= b.min.z
+    }
+    /// Returns the smallest Aabb3 containing both the Aabb3 and `b`
+    pub fn union(&self, b: &Self) -> Self {
+        Aabb3 {
+            min: self.min.min(&b.min),
+            max: self.max.max(&b.max)
+        }
+    }
+    /// Returns the overlapping part of the Aabb3 and `b`, or `None` if they
+    /// do not overlap
+    pub fn intersection(&self, b: &Self) -> Option<Self> {
+        let r = Aabb3 {
+            min: self.min.max(&b.min),
+            max: self.max.min(&b.max)
+        };
+
+        if r.is_empty() { None } else { Some(r) }
+    }
+    /// Returns the point inside the Aabb3 closest to `p`
+    pub fn closest_point(&self, p: Vec3<f32>) -> Vec3<f32> {
+        p.clamp(&self.min, &self.max)
+    }
+    /// Returns a new Aabb3 containing the Aabb3 after transformation by `m`
+    ///
+    /// `m` should be affine (no projection). The result is conservative: it
+    /// contains every transformed point but may be larger than the
+    /// transformed box itself.
+    pub fn transform(&self, m: &Mat4) -> Self {
+        if self.is_empty() {
+            return *self;
+        }
+
+        let a = m.as_array();
+        let c = m.transform_point(self.center());
+        let e = self.extents();
+
+        let e = Vec3::from(
+            a[0].abs()*e.x + a[4].abs()*e.y + a[8].abs()*e.z,
+            a[1].abs()*e.x + a[5].abs()*e.y + a[9].abs()*e.z,
+            a[2].abs()*e.x + a[6].abs()*e.y + a[10].abs()*e.z
+        );
+
+        Aabb3::from_center(c, e)
+    }
+}
+
+/// Mutate methods for Aabb3
+impl Aabb3 {
+    /// Grows the Aabb3 to contain `p`
+    pub fn expand(&mut self, p: Vec3<f32>) {
+        self.min = self.min.min(&p);
+        self.max = self.max.max(&p);
+    }
+    /// Grows the Aabb3 by `amount` on every side
+    pub fn inflate(&mut self, amount: f32) {
+        self.min -= amount;
+        self.max += amount;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Aabb2, Aabb3};
+    use super::super::vec2::Vec2;
+    use super::super::vec3::Vec3;
+    use super::super::mat3::Mat3;
+    use super::super::mat4::Mat4;
+    use super::super::simple::deg;
+
+    fn approx_eq_vec3(a: Vec3<f32>, b: Vec3<f32>) -> bool {
+        (a - b).length() < 0.0001
+    }
+
+    #[test]
+    fn empty_and_expand() {
+        let mut b = Aabb3::empty();
+        assert!(b.is_empty());
+        assert!(!b.contains_point(Vec3::zero()));
+        assert!(!b.intersects(&b));
+
+        b.expand(Vec3::from(1.0, 2.0, 3.0));
+        assert!(!b.is_empty());
+        assert!(b.contains_point(Vec3::from(1.0, 2.0, 3.0)));
+
+        b.expand(Vec3::from(-1.0, 0.0, 5.0));
+        assert_eq!(b, Aabb3::new(Vec3::from(1.0, 0.0, 5.0),
+                                 Vec3::from(-1.0, 2.0, 3.0)));
+
+        let p = Aabb2::from_points(&[Vec2::from(3.0, -1.0), Vec2::from(1.0, 4.0)]);
+        assert_eq!(p.min, Vec2::from(1.0, -1.0));
+        assert_eq!(p.max, Vec2::from(3.0, 4.0));
+        assert!(Aabb2::from_points(&[]).is_empty());
+    }
+    #[test]
+    fn union_intersection() {
+        let a = Aabb3::new(Vec3::zero(), Vec3::from(2.0, 2.0, 2.0));
+        let b = Aabb3::new(Vec3::one(), Vec3::from(3.0, 3.0, 3.0));
+        let c = Aabb3::new(Vec3::from(5.0, 5.0, 5.0), Vec3::from(6.0, 6.0, 6.0));
+
+        assert!(a.intersects(&b) && b.intersects(&a));
+        assert!(!a.intersects(&c));
+
+        assert_eq!(a.intersection(&b),
+                   Some(Aabb3::new(Vec3::one(), Vec3::from(2.0, 2.0, 2.0))));
+        assert_eq!(a.intersection(&c), None);
+
+        let u = a.union(&c);
+        assert_eq!(u, Aabb3::new(Vec3::zero(), Vec3::from(6.0, 6.0, 6.0)));
+        assert!(u.contains(&a) && u.contains(&c) && !a.contains(&u));
+        assert_eq!(a.union(&Aabb3::empty()), a);
+
+        let a = Aabb2::new(Vec2::zero(), Vec2::from(2.0, 2.0));
+        let b = Aabb2::new(Vec2::from(2.0, 1.0), Vec2::from(3.0, 3.0));
+        assert!(a.intersects(&b));
+        assert_eq!(a.intersection(&b).unwrap().size(), Vec2::from(0.0, 1.0));
+    }
+    #[test]
+    fn transform() {
+        let b = Aabb3::new(Vec3::from(-1.0, -2.0, -3.0), Vec3::from(1.0, 2.0, 3.0));
+
+        let mut m = Mat4::new();
+        m.translate(Vec3::from(10.0, 0.0, 0.0));
+        m.rotate_z(deg(90.0));
+
+        let t = b.transform(&m);
+        assert!(approx_eq_vec3(t.min, Vec3::from(8.0, -1.0, -3.0)));
+        assert!(approx_eq_vec3(t.max, Vec3::from(12.0, 1.0, 3.0)));
+
+        // a 45 degree turn grows the box to hold every transformed corner
+        let mut m = Mat4::new();
+        m.rotate_y(deg(45.0));
+        let t = b.transform(&m);
+        for p in b.corners().iter() {
+            let p = m.transform_point(*p);
+            assert!(t.contains_point(p) ||
+                    approx_eq_vec3(t.closest_point(p), p));
+        }
+
+        let b = Aabb2::new(Vec2::zero(), Vec2::from(2.0, 1.0));
+        let mut m = Mat3::new();
+        m.translate(Vec2::from(0.0, 5.0));
+        m.scale(Vec2::from(2.0, 2.0));
+        let t = b.transform(&m);
+        assert!((t.min - Vec2::from(0.0, 5.0)).length() < 0.0001);
+        assert!((t.max - Vec2::from(4.0, 7.0)).length() < 0.0001);
+    }
+}
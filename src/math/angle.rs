@@ -1,10 +1,15 @@
 extern crate num;
 use self::num::Float;
-use std::ops::{Mul, MulAssign, Div, DivAssign, Add, AddAssign, Sub, SubAssign};
+use std::cmp::Ordering;
+use std::ops::{Mul, MulAssign, Div, DivAssign, Add, AddAssign, Sub, SubAssign,
+               Neg};
 
-#[derive(Debug,Copy,Clone,Mul,MulAssign,Div,DivAssign,Add,AddAssign,Sub,SubAssign)]
+#[derive(Debug,Copy,Clone)]
 /// Stores an angle in either degrees or radians
-pub enum Angle<T> where T: Float + Into<T> {
+///
+/// Arithmetic between two Angles keeps the unit of the left hand side, and
+/// comparisons work across units, so `deg(180.0) == rad(PI)`.
+pub enum Angle<T> where T: Float {
     /// Radians
     Radians(T),
     /// Degrees
@@ -16,34 +21,239 @@ pub fn rad<T>(angle: T) -> Angle<T> where T: Float { Angle::Radians(angle) }
 /// Creates a new Angle in degrees
 pub fn deg<T>(angle: T) -> Angle<T> where T: Float { Angle::Degrees(angle) }
 
-impl<T> Angle<T> where T: Float + Into<T> {
+/// Conversion methods for Angle
+impl<T> Angle<T> where T: Float {
     /// Converts self to radians
     pub fn becomes_radians(&mut self) {
-        *self = match *self {
-            Angle::Radians(a) => Angle::Radians(a),
-            Angle::Degrees(a) => Angle::Radians(a*T::from(0.01745329251994329577).unwrap())
-        };
+        *self = self.to_radians();
     }
     /// Converts self to degrees
     pub fn becomes_degrees(&mut self) {
-        *self = match *self {
-            Angle::Radians(a) => Angle::Degrees(a*T::from(57.2957795130823208768).unwrap()),
-            Angle::Degrees(a) => Angle::Degrees(a)
-        };
+        *self = self.to_degrees();
     }
 
     /// Returns this Angle in radians
     pub fn to_radians(&self) -> Self {
         match *self {
             Angle::Radians(a) => Angle::Radians(a),
-            Angle::Degrees(a) => Angle::Radians(a*T::from(0.01745329251994329577).unwrap())
+            Angle::Degrees(a) => Angle::Radians(a.to_radians())
         }
     }
     /// Returns this Angle in degrees
     pub fn to_degrees(&self) -> Self {
         match *self {
-            Angle::Radians(a) => Angle::Degrees(a*T::from(57.2957795130823208768).unwrap()),
+            Angle::Radians(a) => Angle::Degrees(a.to_degrees()),
             Angle::Degrees(a) => Angle::Degrees(a)
         }
     }
+
+    /// Returns the value of this Angle in radians
+    pub fn radians(&self) -> T {
+        match self.to_radians() {
+            Angle::Radians(a) | Angle::Degrees(a) => a
+        }
+    }
+    /// Returns the value of this Angle in degrees
+    pub fn degrees(&self) -> T {
+        match self.to_degrees() {
+            Angle::Radians(a) | Angle::Degrees(a) => a
+        }
+    }
+}
+/// Calc methods for Angle
+impl<T> Angle<T> where T: Float {
+    /// Calculates the sine of the Angle
+    pub fn sin(&self) -> T {
+        self.radians().sin()
+    }
+    /// Calculates the cosine of the Angle
+    pub fn cos(&self) -> T {
+        self.radians().cos()
+    }
+    /// Calculates the tangent of the Angle
+    pub fn tan(&self) -> T {
+        self.radians().tan()
+    }
+    /// Calculates the sine and cosine of the Angle at once, returning
+    /// `(sin, cos)`
+    pub fn sin_cos(&self) -> (T, T) {
+        self.radians().sin_cos()
+    }
+
+    /// Returns this Angle wrapped into one full turn, from 0 up to (but not
+    /// including) 360 degrees
+    pub fn normalized(&self) -> Self {
+        let full = self.full_turn();
+        self.map(|a| {
+            let r = a % full;
+            let r = if r < T::zero() { r + full } else { r };
+            // a tiny negative angle can round up to a whole turn
+            if r >= full { T::zero() } else { r }
+        })
+    }
+    /// Returns this Angle wrapped to the range -180 up to (but not including)
+    /// 180 degrees
+    pub fn wrapped(&self) -> Self {
+        let full = self.full_turn();
+        let half = full/T::from(2.0).unwrap();
+        self.normalized().map(|a| if a >= half { a - full } else { a })
+    }
+
+    fn full_turn(&self) -> T {
+        match *self {
+            Angle::Radians(_) => T::from(::std::f64::consts::PI*2.0).unwrap(),
+            Angle::Degrees(_) => T::from(360.0).unwrap()
+        }
+    }
+    fn map<F>(&self, f: F) -> Self where F: Fn(T) -> T {
+        match *self {
+            Angle::Radians(a) => Angle::Radians(f(a)),
+            Angle::Degrees(a) => Angle::Degrees(f(a))
+        }
+    }
+    // value of `other` in the unit of self
+    fn value_of(&self, other: &Self) -> T {
+        match *self {
+            Angle::Radians(_) => other.radians(),
+            Angle::Degrees(_) => other.degrees()
+        }
+    }
+}
+
+impl<T> PartialEq for Angle<T> where T: Float {
+    fn eq(&self, other: &Self) -> bool {
+        self.radians() == other.radians()
+    }
+}
+impl<T> PartialOrd for Angle<T> where T: Float {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.radians().partial_cmp(&other.radians())
+    }
+}
+impl<T> Neg for Angle<T> where T: Float {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.map(|a| -a)
+    }
+}
+impl<T> Add<Self> for Angle<T> where T: Float {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let b = self.value_of(&rhs);
+        self.map(|a| a + b)
+    }
+}
+impl<T> AddAssign<Self> for Angle<T> where T: Float {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+impl<T> Sub<Self> for Angle<T> where T: Float {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        let b = self.value_of(&rhs);
+        self.map(|a| a - b)
+    }
+}
+impl<T> SubAssign<Self> for Angle<T> where T: Float {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+impl<T> Mul<T> for Angle<T> where T: Float {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        self.map(|a| a*rhs)
+    }
+}
+impl<T> MulAssign<T> for Angle<T> where T: Float {
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self*rhs;
+    }
+}
+impl<T> Div<T> for Angle<T> where T: Float {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self {
+        self.map(|a| a/rhs)
+    }
+}
+impl<T> DivAssign<T> for Angle<T> where T: Float {
+    fn div_assign(&mut self, rhs: T) {
+        *self = *self/rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Angle, rad, deg};
+    use std::f64::consts::PI;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 0.000001
+    }
+
+    #[test]
+    fn convert() {
+        assert!(approx(deg(180.0).radians(), PI));
+        assert!(approx(rad(PI).degrees(), 180.0));
+
+        let mut a = deg(90.0);
+        a.becomes_radians();
+        match a {
+            Angle::Radians(r) => assert!(approx(r, PI/2.0)),
+            Angle::Degrees(_) => panic!("Angle wasn't converted to radians")
+        }
+    }
+    #[test]
+    fn arithmetic() {
+        // the left hand side's unit is kept
+        match deg(90.0) + rad(PI/2.0) {
+            Angle::Degrees(d) => assert!(approx(d, 180.0)),
+            Angle::Radians(_) => panic!("Angle unit changed")
+        }
+        assert!(approx((rad(PI) - deg(90.0)).radians(), PI/2.0));
+        assert!(approx((deg(30.0)*3.0).degrees(), 90.0));
+        assert!(approx((deg(90.0)/2.0).degrees(), 45.0));
+        assert!(approx((-deg(10.0)).degrees(), -10.0));
+
+        let mut a = deg(10.0);
+        a += deg(20.0);
+        a -= deg(5.0);
+        a *= 2.0;
+        a /= 5.0;
+        assert!(approx(a.degrees(), 10.0));
+    }
+    #[test]
+    fn compare() {
+        assert!(deg(90.0f32) == rad(::std::f32::consts::PI/2.0));
+        assert!(deg(10.0) < rad(1.0));
+        assert!(rad(1.0) > deg(10.0));
+    }
+    #[test]
+    fn normalize() {
+        assert!(approx(deg(370.0).normalized().degrees(), 10.0));
+        assert!(approx(deg(-90.0).normalized().degrees(), 270.0));
+        assert!(approx(deg(360.0).normalized().degrees(), 0.0));
+        assert!(approx(rad(3.0*PI).normalized().radians(), PI));
+        assert_eq!(deg(-1e-7f32).normalized().degrees(), 0.0);
+        assert_eq!(rad(-1e-17).normalized().radians(), 0.0);
+
+        assert!(approx(deg(270.0).wrapped().degrees(), -90.0));
+        assert!(approx(deg(-190.0).wrapped().degrees(), 170.0));
+        assert!(approx(deg(180.0).wrapped().degrees(), -180.0));
+    }
+    #[test]
+    fn trig() {
+        assert!(approx(deg(30.0).sin(), 0.5));
+        assert!(approx(deg(60.0).cos(), 0.5));
+        assert!(approx(deg(45.0).tan(), 1.0));
+
+        let (s, c) = rad(PI/2.0).sin_cos();
+        assert!(approx(s, 1.0) && approx(c, 0.0));
+    }
 }
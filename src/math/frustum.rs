@@ -0,0 +1,236 @@
+use super::vec3::Vec3;
+use super::mat4::Mat4;
+use super::plane::Plane;
+use super::aabb::Aabb3;
+use super::sphere::BoundingSphere;
+
+/// Where a shape lies relative to a [`Frustum`](struct.Frustum.html)
+#[derive(Copy,Clone,PartialEq,Eq,Debug)]
+pub enum Containment {
+    /// Entirely inside
+    Inside,
+    /// Entirely outside
+    Outside,
+    /// Partly inside and partly outside
+    Intersecting
+}
+
+/// The volume a camera can see, bounded by six planes
+///
+/// Every plane faces into the frustum and has a normal of length 1. The
+/// exception is a plane at infinity, like the far plane of an infinite
+/// projection, which has a zero normal and an infinite distance so that
+/// nothing is behind it.
+#[derive(Copy,Clone,PartialEq,Debug)]
+pub struct Frustum {
+    /// The planes, in left, right, bottom, top, near and far order
+    pub planes: [Plane; 6]
+}
+
+/// Construct methods for Frustum
+impl Frustum {
+    /// Constructs a new Frustum from six planes facing into it, in left,
+    /// right, bottom, top, near and far order
+    pub fn new(planes: [Plane; 6]) -> Self {
+        Frustum {
+            planes
+        }
+    }
+    /// Extracts the Frustum seen through a view-projection Mat4
+    ///
+    /// `ndc_near` is the clip space depth of the near plane: -1 for the `gl`
+    /// projections, 0 for the `zo` projections and 1 for reverse-z. The
+    /// planes are in the space the view-projection transforms from, usually
+    /// world space.
+    pub fn from_matrix(view_projection: &Mat4, ndc_near: f32) -> Self {
+        let m = view_projection.as_array();
+        let row = |i: usize| [m[i], m[4 + i], m[8 + i], m[12 + i]];
+        let (x, y, z, w) = (row(0), row(1), row(2), row(3));
+
+        let plane = |a: [f32; 4], s: f32, b: [f32; 4]| {
+            let p = Plane::new(Vec3::from(a[0] + s*b[0], a[1] + s*b[1],
+                                          a[2] + s*b[2]),
+                               a[3] + s*b[3]).normalized();
+            if p.normal.length() == 0.0 {
+                Plane::new(p.normal, f32::INFINITY)
+            } else {
+                p
+            }
+        };
+
+        // -w <= z <= w for gl, 0 <= z <= w for zo, and the reverse of zo
+        let (near, far) = if ndc_near < 0.0 {
+            (plane(w, 1.0, z), plane(w, -1.0, z))
+        } else if ndc_near < 1.0 {
+            (plane(z, 0.0, z), plane(w, -1.0, z))
+        } else {
+            (plane(w, -1.0, z), plane(z, 0.0, z))
+        };
+
+        Frustum::new([
+            plane(w,  1.0, x),
+            plane(w, -1.0, x),
+            plane(w,  1.0, y),
+            plane(w, -1.0, y),
+            near,
+            far
+        ])
+    }
+}
+
+/// Calc methods for Frustum
+impl Frustum {
+    /// Returns whether `p` is inside or outside the Frustum
+    ///
+    /// Points on a plane count as inside.
+    pub fn classify_point(&self, p: Vec3<f32>) -> Containment {
+        if self.planes.iter().any(|plane| plane.signed_distance(p) < 0.0) {
+            Containment::Outside
+        } else {
+            Containment::Inside
+        }
+    }
+    /// Returns whether `sphere` is inside, outside or intersecting the
+    /// Frustum
+    pub fn classify_sphere(&self, sphere: &BoundingSphere) -> Containment {
+        if sphere.is_empty() {
+            return Containment::Outside;
+        }
+
+        let mut result = Containment::Inside;
+        for plane in self.planes.iter() {
+            let d = plane.signed_distance(sphere.center);
+            if d < -sphere.radius {
+                return Containment::Outside;
+            }
+            if d < sphere.radius {
+                result = Containment::Intersecting;
+            }
+        }
+        result
+    }
+    /// Returns whether `b` is inside, outside or intersecting the Frustum
+    ///
+    /// Boxes near the frustum's corners may be reported as intersecting when
+    /// they are just outside.
+    pub fn classify_aabb(&self, b: &Aabb3) -> Containment {
+        if b.is_empty() {
+            return Containment::Outside;
+        }
+
+        let mut result = Containment::Inside;
+        for plane in self.planes.iter() {
+            // the corners furthest along and against the normal
+            let n = plane.normal;
+            let far = Vec3::from(if n.x >= 0.0 { b.max.x } else { b.min.x },
+                                 if n.y >= 0.0 { b.max.y } else { b.min.y },
+                                 if n.z >= 0.0 { b.max.z } else { b.min.z });
+            let near = Vec3::from(if n.x >= 0.0 { b.min.x } else { b.max.x },
+                                  if n.y >= 0.0 { b.min.y } else { b.max.y },
+                                  if n.z >= 0.0 { b.min.z } else { b.max.z });
+
+            if plane.signed_distance(far) < 0.0 {
+                return Containment::Outside;
+            }
+            if plane.signed_distance(near) < 0.0 {
+                result = Containment::Intersecting;
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Frustum, Containment};
+    use super::super::vec3::Vec3;
+    use super::super::mat4::Mat4;
+    use super::super::aabb::Aabb3;
+    use super::super::sphere::BoundingSphere;
+    use super::super::simple::deg;
+
+    #[test]
+    fn from_matrix() {
+        let view = Mat4::look_at_rh(Vec3::from(0.0, 0.0, 5.0), Vec3::zero(),
+                                    Vec3::up());
+        let projections = [
+            (Mat4::perspective_rh_gl(deg(90.0), 1.0, 1.0, 100.0), -1.0),
+            (Mat4::perspective_rh_zo(deg(90.0), 1.0, 1.0, 100.0), 0.0),
+            (Mat4::orthographic_rh_gl(-5.0, 5.0, -5.0, 5.0, 1.0, 100.0), -1.0)
+        ];
+
+        for &(proj, ndc_near) in projections.iter() {
+            let f = Frustum::from_matrix(&(view*proj), ndc_near);
+
+            for plane in f.planes.iter() {
+                assert!((plane.normal.length() - 1.0).abs() < 0.0001);
+            }
+
+            // the near plane sits 1 in front of the eye, facing away from it
+            assert!((f.planes[4].signed_distance(Vec3::from(0.0, 0.0, 4.0)))
+                    .abs() < 0.0001);
+            assert!((f.planes[5].signed_distance(Vec3::from(0.0, 0.0, -95.0)))
+                    .abs() < 0.001);
+
+            assert_eq!(f.classify_point(Vec3::zero()), Containment::Inside);
+            assert_eq!(f.classify_point(Vec3::from(0.0, 0.0, 4.5)),
+                       Containment::Outside);
+            assert_eq!(f.classify_point(Vec3::from(0.0, 0.0, -96.0)),
+                       Containment::Outside);
+            assert_eq!(f.classify_point(Vec3::from(0.0, 6.0, 0.0)),
+                       Containment::Outside);
+        }
+    }
+    #[test]
+    fn reverse_z() {
+        let f = Frustum::from_matrix(
+            &Mat4::perspective_infinite_reverse_z(deg(90.0), 1.0, 1.0), 1.0);
+
+        assert_eq!(f.classify_point(Vec3::from(0.0, 0.0, -0.5)),
+                   Containment::Outside);
+        assert_eq!(f.classify_point(Vec3::from(0.0, 0.0, -10000.0)),
+                   Containment::Inside);
+        assert_eq!(f.classify_point(Vec3::from(-11.0, 0.0, -10.0)),
+                   Containment::Outside);
+
+        // the far plane is at infinity and never culls
+        assert_eq!(f.planes[5].normal, Vec3::zero());
+        assert_eq!(f.planes[5].distance, f32::INFINITY);
+        assert_eq!(f.classify_sphere(
+                       &BoundingSphere::new(Vec3::from(0.0, 0.0, -1000.0),
+                                            10.0)),
+                   Containment::Inside);
+        assert_eq!(f.classify_aabb(&Aabb3::new(Vec3::from(-1.0, -1.0, -300.0),
+                                               Vec3::from(1.0, 1.0, -200.0))),
+                   Containment::Inside);
+    }
+    #[test]
+    fn classify_shapes() {
+        let f = Frustum::from_matrix(
+            &Mat4::perspective_rh_gl(deg(90.0), 1.0, 1.0, 100.0), -1.0);
+
+        let s = |x, z, r| BoundingSphere::new(Vec3::from(x, 0.0, z), r);
+        assert_eq!(f.classify_sphere(&s(0.0, -10.0, 1.0)), Containment::Inside);
+        assert_eq!(f.classify_sphere(&s(10.0, -10.0, 1.0)),
+                   Containment::Intersecting);
+        assert_eq!(f.classify_sphere(&s(20.0, -10.0, 1.0)), Containment::Outside);
+        assert_eq!(f.classify_sphere(&s(0.0, -0.5, 1.0)),
+                   Containment::Intersecting);
+        assert_eq!(f.classify_sphere(&BoundingSphere::empty()),
+                   Containment::Outside);
+
+        let b = |min: Vec3<f32>, max: Vec3<f32>| Aabb3::new(min, max);
+        assert_eq!(f.classify_aabb(&b(Vec3::from(-1.0, -1.0, -11.0),
+                                      Vec3::from(1.0, 1.0, -9.0))),
+                   Containment::Inside);
+        assert_eq!(f.classify_aabb(&b(Vec3::from(-1.0, -1.0, -2.0),
+                                      Vec3::from(1.0, 1.0, 2.0))),
+                   Containment::Intersecting);
+        assert_eq!(f.classify_aabb(&b(Vec3::from(-1.0, -1.0, 2.0),
+                                      Vec3::from(1.0, 1.0, 4.0))),
+                   Containment::Outside);
+        assert_eq!(f.classify_aabb(&b(Vec3::from(-1.0, -1.0, -300.0),
+                                      Vec3::from(1.0, 1.0, -200.0))),
+                   Containment::Outside);
+    }
+}
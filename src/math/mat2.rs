@@ -0,0 +1,211 @@
+use std::ops::Mul;
+
+use super::angle::Angle;
+use super::vec2::Vec2;
+
+/// A 2x2 matrix mathematics structure
+///
+/// Used for 2D rotation and scale. The data is stored column major, in the
+/// same order as [`Mat4`](struct.Mat4.html).
+#[derive(Copy,Clone,PartialEq,Debug)]
+pub struct Mat2 {
+    data: [f32; 4]
+}
+
+/// Construct methods for Mat2
+impl Mat2 {
+    /// Constructs a new Mat2 and sets it to the identity matrix
+    pub fn identity() -> Self {
+        Mat2 {
+            data: [1.0, 0.0,
+                   0.0, 1.0]
+        }
+    }
+    /// Constructs a new Mat2 and sets it to identity
+    pub fn new() -> Self {
+        Self::identity()
+    }
+
+    /// Construct a new Mat2 from a 4 element array of f32
+    pub fn from_array(a: [f32; 4]) -> Self {
+        Mat2 {
+            data: a
+        }
+    }
+}
+
+impl Default for Mat2 {
+    fn default() -> Self {
+        Mat2::new()
+    }
+}
+
+/// Get methods for Mat2
+impl Mat2 {
+    /// Returns the Mat2 as an array of 4 f32
+    pub fn as_array(&self) -> [f32; 4] {
+        self.data
+    }
+}
+
+/// Calc methods for Mat2
+impl Mat2 {
+    /// Returns a new Mat2 with rows and columns swapped
+    pub fn transpose(&self) -> Self {
+        let &m = &self.data;
+        Mat2 {
+            data: [m[0], m[2],
+                   m[1], m[3]]
+        }
+    }
+    /// Calculates the determinant of the Mat2
+    pub fn determinant(&self) -> f32 {
+        let &m = &self.data;
+        m[0]*m[3] - m[2]*m[1]
+    }
+    /// Returns the inverse of the Mat2, or `None` if the Mat2 is singular
+    /// (its determinant is zero)
+    pub fn try_inverse(&self) -> Option<Self> {
+        let &m = &self.data;
+
+        let det = self.determinant();
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+
+        let inv_det = 1.0/det;
+        Some(Mat2 {
+            data: [ m[3]*inv_det, -m[1]*inv_det,
+                   -m[2]*inv_det,  m[0]*inv_det]
+        })
+    }
+    /// Returns the inverse of the Mat2
+    ///
+    /// # Panics
+    /// Panics if the Mat2 is singular; use
+    /// [`try_inverse`](#method.try_inverse) when that is possible.
+    pub fn inverse(&self) -> Self {
+        match self.try_inverse() {
+            Some(m) => m,
+            None => panic!("Attempted to invert a singular Mat2!")
+        }
+    }
+}
+
+/// Mutate methods for Mat2
+impl Mat2 {
+    /// Rotate Mat2 by `angle`
+    pub fn rotate(&mut self, angle: Angle<f32>) {
+        let (s, c) = angle.sin_cos();
+        let mut t;
+
+        let m = &mut self.data;
+
+        t = m[0];
+        m[0] = t*c  + m[2]*s;
+        m[2] = t*-s + m[2]*c;
+
+        t = m[1];
+        m[1] = t*c  + m[3]*s;
+        m[3] = t*-s + m[3]*c;
+    }
+    /// Scale Mat2 by Vec2<f32>
+    pub fn scale(&mut self, v: Vec2<f32>) {
+        let m = &mut self.data;
+
+        m[0] *= v.x; m[2] *= v.y;
+        m[1] *= v.x; m[3] *= v.y;
+    }
+
+    /// Set Mat2 rotation
+    pub fn set_rotation(&mut self, angle: Angle<f32>) {
+        let (s, c) = angle.sin_cos();
+
+        let m = &mut self.data;
+
+        m[0] = c; m[2] = -s;
+        m[1] = s; m[3] =  c;
+    }
+    /// Set Mat2 scaling
+    pub fn set_scaling(&mut self, v: Vec2<f32>) {
+        self.data[0] = v.x;
+        self.data[3] = v.y;
+    }
+}
+
+impl Mul for Mat2 {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let &a = &self.data;
+        let &b = &rhs.data;
+        Mat2 {
+            // same order as Mat4; `a * b` applies a and then b
+            data: [
+                a[0]*b[0] + a[1]*b[2],
+                a[0]*b[1] + a[1]*b[3],
+
+                a[2]*b[0] + a[3]*b[2],
+                a[2]*b[1] + a[3]*b[3]
+            ]
+        }
+    }
+}
+impl Mul<Vec2<f32>> for Mat2 {
+    type Output = Vec2<f32>;
+
+    fn mul(self, rhs: Vec2<f32>) -> Vec2<f32> {
+        let &m = &self.data;
+        Vec2 {
+            x: m[0]*rhs.x + m[2]*rhs.y,
+            y: m[1]*rhs.x + m[3]*rhs.y
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Mat2;
+    use super::super::vec2::Vec2;
+    use super::super::simple::deg;
+
+    fn approx_eq(a: &Mat2, b: &Mat2) -> bool {
+        let (a, b) = (a.as_array(), b.as_array());
+        (0..4).all(|i| (a[i] - b[i]).abs() < 0.0001)
+    }
+
+    #[test]
+    fn rotate() {
+        let mut m = Mat2::new();
+        m.rotate(deg(90.0));
+
+        let r = m * Vec2::from(1.0, 0.0);
+        assert!(r.x.abs() < 0.0001 && (r.y - 1.0).abs() < 0.0001);
+
+        let mut s = Mat2::new();
+        s.set_rotation(deg(90.0));
+        assert!(approx_eq(&m, &s));
+    }
+    #[test]
+    fn inverse() {
+        let mut m = Mat2::new();
+        m.rotate(deg(30.0));
+        m.scale(Vec2::from(2.0, 3.0));
+
+        assert!((m.determinant() - 6.0).abs() < 0.0001);
+        assert!(approx_eq(&(m*m.inverse()), &Mat2::identity()));
+        assert!(approx_eq(&m.transpose().transpose(), &m));
+
+        assert!(Mat2::from_array([1.0, 2.0, 2.0, 4.0]).try_inverse().is_none());
+    }
+    #[test]
+    fn multiply_order() {
+        let mut a = Mat2::new();
+        a.scale(Vec2::from(2.0, 1.0));
+        let mut b = Mat2::new();
+        b.rotate(deg(90.0));
+
+        // a then b: scale x, then rotate onto y
+        let r = (a*b) * Vec2::from(1.0, 0.0);
+        assert!(r.x.abs() < 0.0001 && (r.y - 2.0).abs() < 0.0001);
+    }
+}
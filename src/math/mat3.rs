@@ -0,0 +1,368 @@
+use std::ops::Mul;
+
+use super::angle::Angle;
+use super::vec2::Vec2;
+use super::vec3::Vec3;
+use super::mat2::Mat2;
+use super::mat4::Mat4;
+
+/// A 3x3 matrix mathematics structure
+///
+/// Used both for 2D affine transformations, with elements `[6]` and `[7]`
+/// holding the translation, and as the upper 3x3 (rotation and scale) of a
+/// [`Mat4`](struct.Mat4.html). The data is stored column major, in the same
+/// order as Mat4.
+#[derive(Copy,Clone,PartialEq,Debug)]
+pub struct Mat3 {
+    data: [f32; 9]
+}
+
+/// Construct methods for Mat3
+impl Mat3 {
+    /// Constructs a new Mat3 and sets it to the identity matrix
+    pub fn identity() -> Self {
+        Mat3 {
+            data: [1.0, 0.0, 0.0,
+                   0.0, 1.0, 0.0,
+                   0.0, 0.0, 1.0]
+        }
+    }
+    /// Constructs a new Mat3 and sets it to identity
+    pub fn new() -> Self {
+        Self::identity()
+    }
+
+    /// Construct a new Mat3 from a 9 element array of f32
+    pub fn from_array(a: [f32; 9]) -> Self {
+        Mat3 {
+            data: a
+        }
+    }
+    /// Construct a new Mat3 from the upper 3x3 (rotation and scale) of a Mat4
+    pub fn from_mat4(m: &Mat4) -> Self {
+        let m = m.as_array();
+        Mat3 {
+            data: [m[0], m[1], m[ 2],
+                   m[4], m[5], m[ 6],
+                   m[8], m[9], m[10]]
+        }
+    }
+    /// Construct a new Mat3 for transforming normals by a Mat4
+    ///
+    /// This is the inverse transpose of the upper 3x3 of the Mat4, which keeps
+    /// normals perpendicular to their surface under non-uniform scaling.
+    /// Returns `None` if the Mat4 is singular.
+    pub fn normal_matrix(m: &Mat4) -> Option<Self> {
+        Mat3::from_mat4(m).try_inverse().map(|inv| inv.transpose())
+    }
+    /// Construct a new Mat3 from a Mat2, with no translation
+    pub fn from_mat2(m: &Mat2) -> Self {
+        let m = m.as_array();
+        Mat3 {
+            data: [m[0], m[1], 0.0,
+                   m[2], m[3], 0.0,
+                    0.0,  0.0, 1.0]
+        }
+    }
+}
+
+impl Default for Mat3 {
+    fn default() -> Self {
+        Mat3::new()
+    }
+}
+
+/// Get methods for Mat3
+impl Mat3 {
+    /// Returns the Mat3 as an array of 9 f32
+    pub fn as_array(&self) -> [f32; 9] {
+        self.data
+    }
+    /// Returns the Mat3 as the upper 3x3 of a Mat4, with no translation
+    pub fn to_mat4(&self) -> Mat4 {
+        let &m = &self.data;
+        Mat4::from_array([m[0], m[1], m[2], 0.0,
+                          m[3], m[4], m[5], 0.0,
+                          m[6], m[7], m[8], 0.0,
+                           0.0,  0.0,  0.0, 1.0])
+    }
+    /// Returns the upper 2x2 (rotation and scale) of the Mat3
+    pub fn to_mat2(&self) -> Mat2 {
+        let &m = &self.data;
+        Mat2::from_array([m[0], m[1],
+                          m[3], m[4]])
+    }
+}
+
+/// Calc methods for Mat3
+impl Mat3 {
+    /// Returns a new Mat3 with rows and columns swapped
+    pub fn transpose(&self) -> Self {
+        let &m = &self.data;
+        Mat3 {
+            data: [m[0], m[3], m[6],
+                   m[1], m[4], m[7],
+                   m[2], m[5], m[8]]
+        }
+    }
+    /// Calculates the determinant of the Mat3
+    pub fn determinant(&self) -> f32 {
+        let &m = &self.data;
+        let c = self.adjugate();
+
+        m[0]*c[0] + m[1]*c[3] + m[2]*c[6]
+    }
+    /// Returns the inverse of the Mat3, or `None` if the Mat3 is singular
+    /// (its determinant is zero)
+    pub fn try_inverse(&self) -> Option<Self> {
+        let &m = &self.data;
+        let mut c = self.adjugate();
+
+        let det = m[0]*c[0] + m[1]*c[3] + m[2]*c[6];
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+
+        let inv_det = 1.0/det;
+        for e in c.iter_mut() {
+            *e *= inv_det;
+        }
+
+        Some(Mat3 {
+            data: c
+        })
+    }
+    /// Returns the inverse of the Mat3
+    ///
+    /// # Panics
+    /// Panics if the Mat3 is singular; use
+    /// [`try_inverse`](#method.try_inverse) when that is possible.
+    pub fn inverse(&self) -> Self {
+        match self.try_inverse() {
+            Some(m) => m,
+            None => panic!("Attempted to invert a singular Mat3!")
+        }
+    }
+    /// Transforms a 2D point by the Mat3 (translation is applied)
+    pub fn transform_point(&self, p: Vec2<f32>) -> Vec2<f32> {
+        let r = *self * Vec3::from(p.x, p.y, 1.0);
+        Vec2::from(r.x, r.y)
+    }
+    /// Transforms a 2D direction by the Mat3 (translation is ignored)
+    pub fn transform_vector(&self, v: Vec2<f32>) -> Vec2<f32> {
+        let r = *self * Vec3::from(v.x, v.y, 0.0);
+        Vec2::from(r.x, r.y)
+    }
+
+    fn adjugate(&self) -> [f32; 9] {
+        let &m = &self.data;
+        [
+            m[4]*m[8] - m[5]*m[7],
+            m[2]*m[7] - m[1]*m[8],
+            m[1]*m[5] - m[2]*m[4],
+
+            m[5]*m[6] - m[3]*m[8],
+            m[0]*m[8] - m[2]*m[6],
+            m[2]*m[3] - m[0]*m[5],
+
+            m[3]*m[7] - m[4]*m[6],
+            m[1]*m[6] - m[0]*m[7],
+            m[0]*m[4] - m[1]*m[3]
+        ]
+    }
+}
+
+/// Mutate methods for Mat3
+impl Mat3 {
+    /// Translate Mat3 by a Vec2
+    pub fn translate(&mut self, v: Vec2<f32>) {
+        let &m = &self.data;
+        self.data[6] += m[0]*v.x + m[3]*v.y;
+        self.data[7] += m[1]*v.x + m[4]*v.y;
+        self.data[8] += m[2]*v.x + m[5]*v.y;
+    }
+    /// Rotate Mat3 around the (2D) z axis by `angle`
+    pub fn rotate(&mut self, angle: Angle<f32>) {
+        let (s, c) = angle.sin_cos();
+        let mut t;
+
+        let m = &mut self.data;
+
+        t = m[0];
+        m[0] = t*c  + m[3]*s;
+        m[3] = t*-s + m[3]*c;
+
+        t = m[1];
+        m[1] = t*c  + m[4]*s;
+        m[4] = t*-s + m[4]*c;
+
+        t = m[2];
+        m[2] = t*c  + m[5]*s;
+        m[5] = t*-s + m[5]*c;
+    }
+    /// Scale Mat3 by Vec2<f32>
+    pub fn scale(&mut self, v: Vec2<f32>) {
+        let m = &mut self.data;
+
+        m[0] *= v.x; m[3] *= v.y;
+        m[1] *= v.x; m[4] *= v.y;
+        m[2] *= v.x; m[5] *= v.y;
+    }
+
+    /// Set Mat3 translation
+    pub fn set_translation(&mut self, v: Vec2<f32>) {
+        self.data[6] = v.x;
+        self.data[7] = v.y;
+    }
+    /// Set Mat3 rotation around the (2D) z axis
+    pub fn set_rotation(&mut self, angle: Angle<f32>) {
+        let (s, c) = angle.sin_cos();
+
+        let m = &mut self.data;
+
+        m[0] = c; m[3] = -s;
+        m[1] = s; m[4] =  c;
+    }
+    /// Set Mat3 scaling
+    pub fn set_scaling(&mut self, v: Vec2<f32>) {
+        self.data[0] = v.x;
+        self.data[4] = v.y;
+    }
+}
+
+impl Mul for Mat3 {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let &a = &self.data;
+        let &b = &rhs.data;
+        Mat3 {
+            // same order as Mat4; `a * b` applies a and then b
+            data: [
+                a[0]*b[0] + a[1]*b[3] + a[2]*b[6],
+                a[0]*b[1] + a[1]*b[4] + a[2]*b[7],
+                a[0]*b[2] + a[1]*b[5] + a[2]*b[8],
+
+                a[3]*b[0] + a[4]*b[3] + a[5]*b[6],
+                a[3]*b[1] + a[4]*b[4] + a[5]*b[7],
+                a[3]*b[2] + a[4]*b[5] + a[5]*b[8],
+
+                a[6]*b[0] + a[7]*b[3] + a[8]*b[6],
+                a[6]*b[1] + a[7]*b[4] + a[8]*b[7],
+                a[6]*b[2] + a[7]*b[5] + a[8]*b[8]
+            ]
+        }
+    }
+}
+impl Mul<Vec3<f32>> for Mat3 {
+    type Output = Vec3<f32>;
+
+    fn mul(self, rhs: Vec3<f32>) -> Vec3<f32> {
+        let &m = &self.data;
+        Vec3 {
+            x: m[0]*rhs.x + m[3]*rhs.y + m[6]*rhs.z,
+            y: m[1]*rhs.x + m[4]*rhs.y + m[7]*rhs.z,
+            z: m[2]*rhs.x + m[5]*rhs.y + m[8]*rhs.z
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Mat3;
+    use super::super::vec2::Vec2;
+    use super::super::vec3::Vec3;
+    use super::super::mat4::Mat4;
+    use super::super::simple::deg;
+
+    fn approx_eq(a: &Mat3, b: &Mat3) -> bool {
+        let (a, b) = (a.as_array(), b.as_array());
+        (0..9).all(|i| (a[i] - b[i]).abs() < 0.0001)
+    }
+    fn approx_eq_vec2(a: Vec2<f32>, b: Vec2<f32>) -> bool {
+        (a.x - b.x).abs() < 0.0001 && (a.y - b.y).abs() < 0.0001
+    }
+
+    #[test]
+    fn affine_2d() {
+        let mut m = Mat3::new();
+        m.translate(Vec2::from(5.0, 0.0));
+        m.rotate(deg(90.0));
+        m.scale(Vec2::from(2.0, 2.0));
+
+        let p = m.transform_point(Vec2::from(1.0, 0.0));
+        assert!(approx_eq_vec2(p, Vec2::from(5.0, 2.0)));
+
+        let v = m.transform_vector(Vec2::from(1.0, 0.0));
+        assert!(approx_eq_vec2(v, Vec2::from(0.0, 2.0)));
+    }
+    #[test]
+    fn matches_mat4() {
+        let mut m4 = Mat4::new();
+        m4.rotate_z(deg(30.0));
+        m4.scale(Vec3::from(2.0, 3.0, 1.0));
+
+        let mut m3 = Mat3::new();
+        m3.rotate(deg(30.0));
+        m3.scale(Vec2::from(2.0, 3.0));
+
+        assert!(approx_eq(&Mat3::from_mat4(&m4), &m3));
+        assert!(Mat3::from_mat4(&m3.to_mat4()) == m3);
+
+        // multiplication order is the same as Mat4
+        let mut a4 = Mat4::new();
+        a4.rotate_x(deg(40.0));
+        let a3 = Mat3::from_mat4(&a4);
+        assert!(approx_eq(&Mat3::from_mat4(&(a4*m4)), &(a3*m3)));
+    }
+    #[test]
+    fn set_rotation() {
+        let mut a = Mat3::new();
+        a.rotate(deg(75.0));
+
+        let mut b = Mat3::new();
+        b.set_rotation(deg(75.0));
+
+        assert!(approx_eq(&a, &b));
+    }
+    #[test]
+    fn inverse() {
+        let mut m = Mat3::new();
+        m.translate(Vec2::from(3.0, -2.0));
+        m.rotate(deg(30.0));
+        m.scale(Vec2::from(2.0, 0.5));
+
+        assert!(approx_eq(&(m*m.inverse()), &Mat3::identity()));
+        assert!((m.determinant() - 1.0).abs() < 0.0001);
+        assert!(approx_eq(&m.transpose().transpose(), &m));
+
+        assert!(Mat3::from_array([1.0, 2.0, 3.0,
+                                  2.0, 4.0, 6.0,
+                                  0.0, 1.0, 5.0]).try_inverse().is_none());
+    }
+    #[test]
+    fn normal_matrix() {
+        let mut m = Mat4::new();
+        m.scale(Vec3::from(1.0, 4.0, 1.0));
+        m.set_translation(Vec3::from(5.0, 5.0, 5.0));
+
+        // a surface sloping up at 45 degrees is flattened by the scale
+        let tangent = m.transform_vector(Vec3::from(1.0, 1.0, 0.0));
+        let normal = Mat3::normal_matrix(&m).unwrap()
+                     * Vec3::from(-1.0, 1.0, 0.0);
+
+        assert!(tangent.dot(&normal).abs() < 0.0001);
+
+        assert!(Mat3::normal_matrix(&Mat4::from_array([0.0; 16])).is_none());
+    }
+    #[test]
+    fn mat2_conversion() {
+        let mut m = Mat3::new();
+        m.rotate(deg(30.0));
+        m.set_translation(Vec2::from(1.0, 2.0));
+
+        let mut expected = Mat3::from_mat2(&m.to_mat2());
+        expected.set_translation(Vec2::from(1.0, 2.0));
+
+        assert!(approx_eq(&expected, &m));
+    }
+}
@@ -1,7 +1,14 @@
 use std::ops::Mul;
 
+use super::angle::Angle;
+
 /// A 4x4 matrix mathematics structure
-#[derive(Copy,Clone,PartialEq)]
+///
+/// The data is stored column major; element `[12]`, `[13]` and `[14]` hold the
+/// translation. Mutate methods such as [`translate`](#method.translate) and
+/// [`rotate`](#method.rotate) apply their transformation before (inside) the
+/// existing one.
+#[derive(Copy,Clone,PartialEq,Debug)]
 pub struct Mat4 {
     data: [f32; 16]
 }
@@ -32,6 +39,154 @@ impl Mat4 {
     }
 }
 
+impl Default for Mat4 {
+    fn default() -> Self {
+        Mat4::new()
+    }
+}
+
+/// Projection and view construct methods for Mat4
+///
+/// Each projection is named by its handedness and its clip space depth range:
+///
+/// * `rh` (right handed) looks down -z, as OpenGL and glium expect
+/// * `lh` (left handed) looks down +z
+/// * `gl` maps depth from `z_near` and `z_far` to -1 and 1 (OpenGL)
+/// * `zo` maps depth from `z_near` and `z_far` to 0 and 1 (Vulkan, Direct3D)
+impl Mat4 {
+    /// Constructs a right handed perspective projection with -1 to 1 depth and
+    /// a vertical field of view `fov`
+    pub fn perspective_rh_gl(fov: Angle<f32>, aspect: f32, z_near: f32,
+                             z_far: f32) -> Self {
+        Mat4::perspective(fov, aspect,
+                          (z_far + z_near)/(z_near - z_far), -1.0,
+                          2.0*z_far*z_near/(z_near - z_far))
+    }
+    /// Constructs a right handed perspective projection with 0 to 1 depth and
+    /// a vertical field of view `fov`
+    pub fn perspective_rh_zo(fov: Angle<f32>, aspect: f32, z_near: f32,
+                             z_far: f32) -> Self {
+        Mat4::perspective(fov, aspect,
+                          z_far/(z_near - z_far), -1.0,
+                          z_near*z_far/(z_near - z_far))
+    }
+    /// Constructs a left handed perspective projection with -1 to 1 depth and
+    /// a vertical field of view `fov`
+    pub fn perspective_lh_gl(fov: Angle<f32>, aspect: f32, z_near: f32,
+                             z_far: f32) -> Self {
+        Mat4::perspective(fov, aspect,
+                          (z_far + z_near)/(z_far - z_near), 1.0,
+                          -2.0*z_far*z_near/(z_far - z_near))
+    }
+    /// Constructs a left handed perspective projection with 0 to 1 depth and
+    /// a vertical field of view `fov`
+    pub fn perspective_lh_zo(fov: Angle<f32>, aspect: f32, z_near: f32,
+                             z_far: f32) -> Self {
+        Mat4::perspective(fov, aspect,
+                          z_far/(z_far - z_near), 1.0,
+                          -z_near*z_far/(z_far - z_near))
+    }
+    /// Constructs a right handed perspective projection with no far plane
+    /// and reversed 0 to 1 depth and a vertical field of view `fov`
+    ///
+    /// `z_near` maps to a depth of 1 and infinitely far away maps to 0, which
+    /// spreads floating point depth precision evenly. Use a depth test of
+    /// "greater" and clear depth to 0.
+    pub fn perspective_infinite_reverse_z(fov: Angle<f32>, aspect: f32,
+                                          z_near: f32) -> Self {
+        Mat4::perspective(fov, aspect, 0.0, -1.0, z_near)
+    }
+
+    /// Constructs a right handed orthographic projection with -1 to 1 depth
+    pub fn orthographic_rh_gl(left: f32, right: f32, bottom: f32, top: f32,
+                              z_near: f32, z_far: f32) -> Self {
+        Mat4::orthographic(left, right, bottom, top,
+                           -2.0/(z_far - z_near),
+                           -(z_far + z_near)/(z_far - z_near))
+    }
+    /// Constructs a right handed orthographic projection with 0 to 1 depth
+    pub fn orthographic_rh_zo(left: f32, right: f32, bottom: f32, top: f32,
+                              z_near: f32, z_far: f32) -> Self {
+        Mat4::orthographic(left, right, bottom, top,
+                           -1.0/(z_far - z_near),
+                           -z_near/(z_far - z_near))
+    }
+    /// Constructs a left handed orthographic projection with -1 to 1 depth
+    pub fn orthographic_lh_gl(left: f32, right: f32, bottom: f32, top: f32,
+                              z_near: f32, z_far: f32) -> Self {
+        Mat4::orthographic(left, right, bottom, top,
+                           2.0/(z_far - z_near),
+                           -(z_far + z_near)/(z_far - z_near))
+    }
+    /// Constructs a left handed orthographic projection with 0 to 1 depth
+    pub fn orthographic_lh_zo(left: f32, right: f32, bottom: f32, top: f32,
+                              z_near: f32, z_far: f32) -> Self {
+        Mat4::orthographic(left, right, bottom, top,
+                           1.0/(z_far - z_near),
+                           -z_near/(z_far - z_near))
+    }
+
+    /// Constructs a right handed view matrix at `eye` looking at `target`
+    pub fn look_at_rh(eye: super::vec3::Vec3<f32>,
+                      target: super::vec3::Vec3<f32>,
+                      up: super::vec3::Vec3<f32>) -> Self {
+        let f = (target - eye).normalized();
+        let s = f.cross(up).normalized();
+        let u = s.cross(f);
+
+        Mat4 {
+            data: [s.x, u.x, -f.x, 0.0,
+                   s.y, u.y, -f.y, 0.0,
+                   s.z, u.z, -f.z, 0.0,
+                   -s.dot(&eye), -u.dot(&eye), f.dot(&eye), 1.0]
+        }
+    }
+    /// Constructs a left handed view matrix at `eye` looking at `target`
+    pub fn look_at_lh(eye: super::vec3::Vec3<f32>,
+                      target: super::vec3::Vec3<f32>,
+                      up: super::vec3::Vec3<f32>) -> Self {
+        let f = (target - eye).normalized();
+        let s = up.cross(f).normalized();
+        let u = f.cross(s);
+
+        Mat4 {
+            data: [s.x, u.x, f.x, 0.0,
+                   s.y, u.y, f.y, 0.0,
+                   s.z, u.z, f.z, 0.0,
+                   -s.dot(&eye), -u.dot(&eye), -f.dot(&eye), 1.0]
+        }
+    }
+
+    fn perspective(fov: Angle<f32>, aspect: f32, m10: f32, m11: f32, m14: f32)
+                   -> Self {
+        let b = 1f32/(fov*0.5).tan();
+        let a = b/aspect;
+
+        Mat4 {
+            data: [  a, 0.0, 0.0, 0.0,
+                   0.0,   b, 0.0, 0.0,
+                   0.0, 0.0, m10, m11,
+                   0.0, 0.0, m14, 0.0]
+        }
+    }
+    fn orthographic(left: f32, right: f32, bottom: f32, top: f32, m10: f32,
+                    m14: f32) -> Self {
+        let (a,b,c,d);
+
+        a =             2.0/(right  - left);
+        b = (left + right )/(left   - right);
+        c =             2.0/(top    - bottom);
+        d = (top  + bottom)/(bottom - top);
+
+        Mat4 {
+            data: [  a, 0.0, 0.0, 0.0,
+                   0.0,   c, 0.0, 0.0,
+                   0.0, 0.0, m10, 0.0,
+                     b,   d, m14, 1.0]
+        }
+    }
+}
+
 /// Get methods for Mat4
 impl Mat4 {
     /// Returns the Mat4 as an array of 16 f32
@@ -40,6 +195,173 @@ impl Mat4 {
     }
 }
 
+/// Calc methods for Mat4
+impl Mat4 {
+    /// Returns a new Mat4 with rows and columns swapped
+    pub fn transpose(&self) -> Self {
+        let &m = &self.data;
+        Mat4 {
+            data: [m[ 0], m[ 4], m[ 8], m[12],
+                   m[ 1], m[ 5], m[ 9], m[13],
+                   m[ 2], m[ 6], m[10], m[14],
+                   m[ 3], m[ 7], m[11], m[15]]
+        }
+    }
+    /// Calculates the determinant of the Mat4
+    pub fn determinant(&self) -> f32 {
+        let &m = &self.data;
+        let c = self.adjugate();
+
+        m[0]*c[0] + m[1]*c[4] + m[2]*c[8] + m[3]*c[12]
+    }
+    /// Returns the inverse of the Mat4, or `None` if the Mat4 is singular
+    /// (its determinant is zero)
+    pub fn try_inverse(&self) -> Option<Self> {
+        let &m = &self.data;
+        let mut c = self.adjugate();
+
+        let det = m[0]*c[0] + m[1]*c[4] + m[2]*c[8] + m[3]*c[12];
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+
+        let inv_det = 1.0/det;
+        for e in c.iter_mut() {
+            *e *= inv_det;
+        }
+
+        Some(Mat4 {
+            data: c
+        })
+    }
+    /// Returns the inverse of the Mat4
+    ///
+    /// # Panics
+    /// Panics if the Mat4 is singular; use
+    /// [`try_inverse`](#method.try_inverse) when that is possible.
+    pub fn inverse(&self) -> Self {
+        match self.try_inverse() {
+            Some(m) => m,
+            None => panic!("Attempted to invert a singular Mat4!")
+        }
+    }
+    /// Transforms a point by the Mat4 (translation is applied)
+    ///
+    /// If the Mat4 is a projection the result is divided by the resulting w.
+    pub fn transform_point(&self, p: super::vec3::Vec3<f32>)
+                           -> super::vec3::Vec3<f32> {
+        let r = *self * super::vec4::Vec4::from(p.x, p.y, p.z, 1.0);
+
+        if r.w != 1.0 && r.w != 0.0 {
+            super::vec3::Vec3::from(r.x/r.w, r.y/r.w, r.z/r.w)
+        } else {
+            super::vec3::Vec3::from(r.x, r.y, r.z)
+        }
+    }
+    /// Transforms a direction by the Mat4 (translation is ignored)
+    pub fn transform_vector(&self, v: super::vec3::Vec3<f32>)
+                            -> super::vec3::Vec3<f32> {
+        *self * v
+    }
+    /// Splits the Mat4 into its translation, rotation and scale
+    ///
+    /// The rotation is returned as Angles as accepted by
+    /// [`rotate`](#method.rotate) and [`set_rotation`](#method.set_rotation),
+    /// so that translating, rotating and then scaling an identity Mat4 by the
+    /// returned values rebuilds this Mat4. Shear and projection are discarded.
+    pub fn decompose(&self) -> (super::vec3::Vec3<f32>,
+                                super::vec3::Vec3<Angle<f32>>,
+                                super::vec3::Vec3<f32>) {
+        use super::vec3::Vec3;
+
+        let &m = &self.data;
+
+        let translation = Vec3::from(m[12], m[13], m[14]);
+
+        let mut scale = Vec3::from(
+            Vec3::from(m[0], m[1], m[ 2]).length(),
+            Vec3::from(m[4], m[5], m[ 6]).length(),
+            Vec3::from(m[8], m[9], m[10]).length()
+        );
+
+        // a negative 3x3 determinant means one axis is mirrored
+        let det3 = m[0]*(m[5]*m[10] - m[6]*m[9])
+                 - m[4]*(m[1]*m[10] - m[2]*m[9])
+                 + m[8]*(m[1]*m[ 6] - m[2]*m[5]);
+        if det3 < 0.0 {
+            scale.x = -scale.x;
+        }
+
+        // rotation matrix elements r[row][column]
+        let r00 = m[0]/scale.x;
+        let r20 = m[2]/scale.x;
+        let r01 = m[4]/scale.y;
+        let r11 = m[5]/scale.y;
+        let r21 = m[6]/scale.y;
+        let r02 = m[8]/scale.z;
+        let r22 = m[10]/scale.z;
+
+        let x = r21.clamp(-1.0, 1.0).asin();
+        let (y, z);
+
+        if r21.abs() < 0.99999 {
+            y = r20.atan2(r22);
+            z = (-r01).atan2(r11);
+        } else {
+            // gimbal lock; z and y rotate around the same axis
+            y = -r02.atan2(r00);
+            z = 0.0;
+        }
+
+        let rotation = Vec3::from(Angle::Radians(x).to_degrees(),
+                                  Angle::Radians(y).to_degrees(),
+                                  Angle::Radians(z).to_degrees());
+
+        (translation, rotation, scale)
+    }
+
+    fn adjugate(&self) -> [f32; 16] {
+        let &m = &self.data;
+        [
+             m[5]*m[10]*m[15] - m[5]*m[11]*m[14] - m[9]*m[6]*m[15]
+                + m[9]*m[7]*m[14] + m[13]*m[6]*m[11] - m[13]*m[7]*m[10],
+            -m[1]*m[10]*m[15] + m[1]*m[11]*m[14] + m[9]*m[2]*m[15]
+                - m[9]*m[3]*m[14] - m[13]*m[2]*m[11] + m[13]*m[3]*m[10],
+             m[1]*m[ 6]*m[15] - m[1]*m[ 7]*m[14] - m[5]*m[2]*m[15]
+                + m[5]*m[3]*m[14] + m[13]*m[2]*m[ 7] - m[13]*m[3]*m[ 6],
+            -m[1]*m[ 6]*m[11] + m[1]*m[ 7]*m[10] + m[5]*m[2]*m[11]
+                - m[5]*m[3]*m[10] - m[ 9]*m[2]*m[ 7] + m[ 9]*m[3]*m[ 6],
+
+            -m[4]*m[10]*m[15] + m[4]*m[11]*m[14] + m[8]*m[6]*m[15]
+                - m[8]*m[7]*m[14] - m[12]*m[6]*m[11] + m[12]*m[7]*m[10],
+             m[0]*m[10]*m[15] - m[0]*m[11]*m[14] - m[8]*m[2]*m[15]
+                + m[8]*m[3]*m[14] + m[12]*m[2]*m[11] - m[12]*m[3]*m[10],
+            -m[0]*m[ 6]*m[15] + m[0]*m[ 7]*m[14] + m[4]*m[2]*m[15]
+                - m[4]*m[3]*m[14] - m[12]*m[2]*m[ 7] + m[12]*m[3]*m[ 6],
+             m[0]*m[ 6]*m[11] - m[0]*m[ 7]*m[10] - m[4]*m[2]*m[11]
+                + m[4]*m[3]*m[10] + m[ 8]*m[2]*m[ 7] - m[ 8]*m[3]*m[ 6],
+
+             m[4]*m[ 9]*m[15] - m[4]*m[11]*m[13] - m[8]*m[5]*m[15]
+                + m[8]*m[7]*m[13] + m[12]*m[5]*m[11] - m[12]*m[7]*m[ 9],
+            -m[0]*m[ 9]*m[15] + m[0]*m[11]*m[13] + m[8]*m[1]*m[15]
+                - m[8]*m[3]*m[13] - m[12]*m[1]*m[11] + m[12]*m[3]*m[ 9],
+             m[0]*m[ 5]*m[15] - m[0]*m[ 7]*m[13] - m[4]*m[1]*m[15]
+                + m[4]*m[3]*m[13] + m[12]*m[1]*m[ 7] - m[12]*m[3]*m[ 5],
+            -m[0]*m[ 5]*m[11] + m[0]*m[ 7]*m[ 9] + m[4]*m[1]*m[11]
+                - m[4]*m[3]*m[ 9] - m[ 8]*m[1]*m[ 7] + m[ 8]*m[3]*m[ 5],
+
+            -m[4]*m[ 9]*m[14] + m[4]*m[10]*m[13] + m[8]*m[5]*m[14]
+                - m[8]*m[6]*m[13] - m[12]*m[5]*m[10] + m[12]*m[6]*m[ 9],
+             m[0]*m[ 9]*m[14] - m[0]*m[10]*m[13] - m[8]*m[1]*m[14]
+                + m[8]*m[2]*m[13] + m[12]*m[1]*m[10] - m[12]*m[2]*m[ 9],
+            -m[0]*m[ 5]*m[14] + m[0]*m[ 6]*m[13] + m[4]*m[1]*m[14]
+                - m[4]*m[2]*m[13] - m[12]*m[1]*m[ 6] + m[12]*m[2]*m[ 5],
+             m[0]*m[ 5]*m[10] - m[0]*m[ 6]*m[ 9] - m[4]*m[1]*m[10]
+                + m[4]*m[2]*m[ 9] + m[ 8]*m[1]*m[ 6] - m[ 8]*m[2]*m[ 5]
+        ]
+    }
+}
+
 /// Mutate methods for Mat4
 impl Mat4 {
     /// Translate Mat4 by a Vec3
@@ -49,14 +371,14 @@ impl Mat4 {
         self.data[13] += m[ 1]*v.x + m[ 5]*v.y + m[ 9]*v.z;
         self.data[14] += m[ 2]*v.x + m[ 6]*v.y + m[10]*v.z;
     }
-    /// Rotate Mat4 around the x axis by angle in degrees
-    pub fn rotate_x(&mut self, angle: f32) {
+    /// Rotate Mat4 around the x axis by `angle`
+    pub fn rotate_x(&mut self, angle: Angle<f32>) {
         let (c, s, mut t);
 
         let m = &mut self.data;
 
-        c = angle.to_radians().cos();
-        s = angle.to_radians().sin();
+        c = angle.cos();
+        s = angle.sin();
 
         t = m[ 4];
         m[ 4] = t*c  + m[ 8]*s;
@@ -70,14 +392,14 @@ impl Mat4 {
         m[ 6] = t*c  + m[10]*s;
         m[10] = t*-s + m[10]*c;
     }
-    /// Rotate Mat4 around the y axis by angle in degrees
-    pub fn rotate_y(&mut self, angle: f32) {
+    /// Rotate Mat4 around the y axis by `angle`
+    pub fn rotate_y(&mut self, angle: Angle<f32>) {
         let (c, s, mut t);
 
         let m = &mut self.data;
 
-        c = angle.to_radians().cos();
-        s = angle.to_radians().sin();
+        c = angle.cos();
+        s = angle.sin();
 
         t = m[ 0];
         m[ 0] = t*c  + m[ 8]*s;
@@ -91,14 +413,14 @@ impl Mat4 {
         m[ 2] = t*c  + m[10]*s;
         m[10] = t*-s + m[10]*c;
     }
-    /// Rotate Mat4 around the z axis by angle in degrees
-    pub fn rotate_z(&mut self, angle: f32) {
+    /// Rotate Mat4 around the z axis by `angle`
+    pub fn rotate_z(&mut self, angle: Angle<f32>) {
         let (c, s, mut t);
 
         let m = &mut self.data;
 
-        c = angle.to_radians().cos();
-        s = angle.to_radians().sin();
+        c = angle.cos();
+        s = angle.sin();
 
         t = m[ 0];
         m[ 0] = t*c  + m[ 4]*s;
@@ -112,8 +434,8 @@ impl Mat4 {
         m[ 2] = t*c  + m[ 6]*s;
         m[ 6] = t*-s + m[ 6]*c;
     }
-    /// Rotate Mat4 by Vec3<f32> in degrees
-    pub fn rotate(&mut self, v: super::vec3::Vec3<f32>) {
+    /// Rotate Mat4 by a Vec3 of Angles around the z, x and then y axes
+    pub fn rotate(&mut self, v: super::vec3::Vec3<Angle<f32>>) {
         self.rotate_z(v.z);
         self.rotate_x(v.x);
         self.rotate_y(v.y);
@@ -122,9 +444,10 @@ impl Mat4 {
     pub fn scale(&mut self, v: super::vec3::Vec3<f32>) {
         let m = &mut self.data;
 
-        m[0] *= v.x; m[4] *= v.x; m[ 8] *= v.x;
-        m[1] *= v.y; m[5] *= v.y; m[ 9] *= v.y;
-        m[2] *= v.z; m[6] *= v.z; m[10] *= v.z;
+        m[0] *= v.x; m[4] *= v.y; m[ 8] *= v.z;
+        m[1] *= v.x; m[5] *= v.y; m[ 9] *= v.z;
+        m[2] *= v.x; m[6] *= v.y; m[10] *= v.z;
+        m[3] *= v.x; m[7] *= v.y; m[11] *= v.z;
     }
 
     /// Set Mat4 translation
@@ -133,20 +456,19 @@ impl Mat4 {
         self.data[13] = v.y;
         self.data[14] = v.z;
     }
-    /// Set Mat4 rotation in degrees
-    pub fn set_rotation(&mut self, v: super::vec3::Vec3<f32>) {
-        let (x,y,z) = (v.x.to_radians(), v.y.to_radians(), v.z.to_radians());
+    /// Set Mat4 rotation from a Vec3 of Angles, matching
+    /// [`rotate`](#method.rotate)
+    pub fn set_rotation(&mut self, v: super::vec3::Vec3<Angle<f32>>) {
         let (sx,sy,sz, cx,cy,cz);
-        let cz_nsx;
 
-        sx=x.sin(); sy=y.sin(); sz=z.sin();
-        cx=x.cos(); cy=y.cos(); cz=z.cos();
+        sx=v.x.sin(); sy=v.y.sin(); sz=v.z.sin();
+        cx=v.x.cos(); cy=v.y.cos(); cz=v.z.cos();
 
-        cz_nsx = cz*-sx;
+        let cz_nsx = cz*-sx;
 
         let m = &mut self.data;
 
-        m[ 0] = cz*cy;             m[ 4] = -sz*cx; m[ 8] = cz*-sy + sz*sx*cy;
+        m[ 0] = cz*cy + sz*sx*sy;  m[ 4] = -sz*cx; m[ 8] = cz*-sy + sz*sx*cy;
         m[ 1] = sz*cy + cz_nsx*sy; m[ 5] =  cz*cx; m[ 9] = sz*-sy + cz_nsx*cy;
         m[ 2] = cx*sy;             m[ 6] =  sx;    m[10] = cx*cy;
     }
@@ -156,40 +478,25 @@ impl Mat4 {
         self.data[ 5] = v.y;
         self.data[10] = v.z;
     }
-    /// Set Mat4 orthographic projection
+    /// Set Mat4 to a left handed orthographic projection with 0 to 1 depth
+    ///
+    /// All elements are replaced; see
+    /// [`orthographic_lh_zo`](#method.orthographic_lh_zo) and the other
+    /// orthographic constructors.
     pub fn set_orthographic(&mut self, left: f32, right: f32, bottom: f32,
                             top: f32, z_near: f32, z_far: f32) {
-        let (a,b,c,d,e,f);
-
-        a =             2.0/(right  - left);
-        b = (left + right )/(left   - right);
-        c =             2.0/(top    - bottom);
-        d = (top  + bottom)/(bottom - top);
-        e =             1.0/(z_far  - z_near);
-        f =          z_near/(z_near - z_far);
-
-        self.data[ 0] = a;
-        self.data[12] = b;
-        self.data[ 5] = c;
-        self.data[13] = d;
-        self.data[10] = e;
-        self.data[14] = f;
-    }
-    /// Set Mat4 perspective projection
-    pub fn set_perspective(&mut self, fov: super::Angle<f32>, aspect: f32, z_near: f32,
+        *self = Mat4::orthographic_lh_zo(left, right, bottom, top, z_near,
+                                         z_far);
+    }
+    /// Set Mat4 to a left handed perspective projection with 0 to 1 depth and
+    /// a vertical field of view `fov`
+    ///
+    /// All elements are replaced; see
+    /// [`perspective_lh_zo`](#method.perspective_lh_zo) and the other
+    /// perspective constructors.
+    pub fn set_perspective(&mut self, fov: Angle<f32>, aspect: f32, z_near: f32,
                            z_far: f32) {
-        let (a,b,c,d);
-
-        b = 1f32/(fov*0.5).to_radians().tan();
-        a = b/aspect;
-
-        c =         z_far/(z_far - z_near);
-        d = -z_near*z_far/(z_far - z_near);
-
-        self.data[ 0] = a;
-        self.data[ 5] = b;
-        self.data[10] = c;
-        self.data[14] = d;
+        *self = Mat4::perspective_lh_zo(fov, aspect, z_near, z_far);
     }
 }
 
@@ -253,11 +560,36 @@ impl Mul<super::vec3::Vec3<f32>> for Mat4 {
         }
     }
 }
+impl Mul<super::vec4::Vec4<f32>> for Mat4 {
+    type Output = super::vec4::Vec4<f32>;
+
+    fn mul(self, rhs: super::vec4::Vec4<f32>) -> super::vec4::Vec4<f32> {
+        let &m = &self.data;
+        super::vec4::Vec4 {
+            x: m[0]*rhs.x + m[4]*rhs.y + m[ 8]*rhs.z + m[12]*rhs.w,
+            y: m[1]*rhs.x + m[5]*rhs.y + m[ 9]*rhs.z + m[13]*rhs.w,
+            z: m[2]*rhs.x + m[6]*rhs.y + m[10]*rhs.z + m[14]*rhs.w,
+            w: m[3]*rhs.x + m[7]*rhs.y + m[11]*rhs.z + m[15]*rhs.w
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::Mat4;
     use super::super::vec3::Vec3;
+    use super::super::vec4::Vec4;
+    use super::super::simple::deg;
+
+    fn approx_eq(a: &Mat4, b: &Mat4) -> bool {
+        let (a, b) = (a.as_array(), b.as_array());
+        (0..16).all(|i| (a[i] - b[i]).abs() < 0.0001)
+    }
+    fn approx_eq_vec3(a: Vec3<f32>, b: Vec3<f32>) -> bool {
+        (a.x - b.x).abs() < 0.001
+            && (a.y - b.y).abs() < 0.001
+            && (a.z - b.z).abs() < 0.001
+    }
 
     #[test]
     fn matrix_multiply() {
@@ -313,7 +645,7 @@ mod tests {
     fn vec3_multiply() {
         let mut m = Mat4::new();
 
-        m.rotate_y(90f32);
+        m.rotate_y(deg(90.0));
 
         let r = m * Vec3::from(32.0, 0.0, 42.0);
 
@@ -327,7 +659,7 @@ mod tests {
         let mut m = Mat4::new();
 
         // set rotation around z axis of 90 degrees
-        m.rotate_z(90f32);
+        m.rotate_z(deg(90.0));
 
         // translate by 5 on x
         m.translate(Vec3::from(5.0, 0.0, 0.0));
@@ -348,7 +680,7 @@ mod tests {
         let mut m = Mat4::new();
 
         // set rotation around x axis of 90 degrees
-        m.rotate_x(90f32);
+        m.rotate_x(deg(90.0));
 
         // translate by 5 on x
         m.translate(Vec3::from(0.0, 5.0, 0.0));
@@ -369,7 +701,7 @@ mod tests {
         let mut m = Mat4::new();
 
         // set rotation around y axis of 90 degrees
-        m.rotate_y(90f32);
+        m.rotate_y(deg(90.0));
 
         // translate by 5 on z
         m.translate(Vec3::from(0.0, 0.0, 5.0));
@@ -385,4 +717,283 @@ mod tests {
         // check y is ~zero
         assert!((arr[13] -  0.0).abs() < 0.00001);
     }
+    #[test]
+    fn transpose() {
+        let m = Mat4::from_array([
+             1.0,  2.0,  3.0,  4.0,
+             5.0,  6.0,  7.0,  8.0,
+             9.0, 10.0, 11.0, 12.0,
+            13.0, 14.0, 15.0, 16.0f32
+        ]);
+
+        let t = m.transpose().as_array();
+
+        assert_eq!(t[ 1],  5.0);
+        assert_eq!(t[ 4],  2.0);
+        assert_eq!(t[ 3], 13.0);
+        assert_eq!(t[12],  4.0);
+        assert_eq!(t[ 0],  1.0);
+        assert_eq!(t[15], 16.0);
+
+        assert!(m.transpose().transpose() == m);
+    }
+    #[test]
+    fn determinant() {
+        assert!((Mat4::identity().determinant() - 1.0).abs() < 0.00001);
+
+        let mut m = Mat4::new();
+        m.set_scaling(Vec3::from(2.0, 3.0, 4.0));
+        assert!((m.determinant() - 24.0).abs() < 0.00001);
+
+        // rotation and translation don't change the volume
+        m.rotate(Vec3::from(deg(30.0), deg(45.0), deg(60.0)));
+        m.translate(Vec3::from(3.0, -2.0, 7.0));
+        assert!((m.determinant() - 24.0).abs() < 0.001);
+
+        let m = Mat4::from_array([
+            2.0, 0.0, 1.0, 3.0,
+            1.0, 1.0, 0.0, 2.0,
+            0.0, 4.0, 1.0, 1.0,
+            3.0, 1.0, 2.0, 0.0f32
+        ]);
+        assert!((m.determinant() - -28.0).abs() < 0.0001);
+        assert!((m.transpose().determinant() - -28.0).abs() < 0.0001);
+    }
+    #[test]
+    fn inverse() {
+        let mut m = Mat4::new();
+        m.translate(Vec3::from(3.0, -2.0, 7.0));
+        m.rotate(Vec3::from(deg(30.0), deg(45.0), deg(60.0)));
+        m.scale(Vec3::from(2.0, 0.5, 4.0));
+
+        let inv = m.inverse();
+
+        assert!(approx_eq(&(m*inv), &Mat4::identity()));
+        assert!(approx_eq(&(inv*m), &Mat4::identity()));
+
+        let m = Mat4::from_array([
+            2.0, 0.0, 1.0, 3.0,
+            1.0, 1.0, 0.0, 2.0,
+            0.0, 4.0, 1.0, 1.0,
+            3.0, 1.0, 2.0, 0.0f32
+        ]);
+
+        assert!(approx_eq(&(m*m.inverse()), &Mat4::identity()));
+        assert!(approx_eq(&m.inverse().inverse(), &m));
+    }
+    #[test]
+    fn try_inverse_singular() {
+        let m = Mat4::from_array([
+            1.0, 2.0, 3.0, 4.0,
+            2.0, 4.0, 6.0, 8.0,
+            0.0, 1.0, 0.0, 1.0,
+            5.0, 0.0, 5.0, 0.0f32
+        ]);
+
+        assert_eq!(m.determinant(), 0.0);
+        assert!(m.try_inverse().is_none());
+        assert!(Mat4::identity().try_inverse() == Some(Mat4::identity()));
+    }
+    #[test]
+    #[should_panic]
+    fn inverse_singular_panics() {
+        Mat4::from_array([0.0; 16]).inverse();
+    }
+    #[test]
+    fn vec4_multiply() {
+        let mut m = Mat4::new();
+        m.translate(Vec3::from(1.0, 2.0, 3.0));
+
+        let p = m * Vec4::from(1.0, 1.0, 1.0, 1.0);
+        assert_eq!((p.x, p.y, p.z, p.w), (2.0, 3.0, 4.0, 1.0));
+
+        let d = m * Vec4::from(1.0, 1.0, 1.0, 0.0);
+        assert_eq!((d.x, d.y, d.z, d.w), (1.0, 1.0, 1.0, 0.0));
+    }
+    #[test]
+    fn transform_point_vector() {
+        let mut m = Mat4::new();
+        m.translate(Vec3::from(5.0, 0.0, 0.0));
+        m.rotate_z(deg(90.0));
+
+        let p = m.transform_point(Vec3::from(1.0, 0.0, 0.0));
+        assert!(approx_eq_vec3(p, Vec3::from(5.0, 1.0, 0.0)));
+
+        let v = m.transform_vector(Vec3::from(1.0, 0.0, 0.0));
+        assert!(approx_eq_vec3(v, Vec3::from(0.0, 1.0, 0.0)));
+
+        // w is divided out of points
+        let mut arr = Mat4::new().as_array();
+        arr[15] = 2.0;
+        let p = Mat4::from_array(arr).transform_point(Vec3::from(2.0, 4.0, 6.0));
+        assert!(approx_eq_vec3(p, Vec3::from(1.0, 2.0, 3.0)));
+    }
+    #[test]
+    fn set_rotation_matches_rotate() {
+        let r = Vec3::from(deg(30.0), deg(45.0), deg(60.0));
+
+        let mut a = Mat4::new();
+        a.rotate(r);
+
+        let mut b = Mat4::new();
+        b.set_rotation(r);
+
+        assert!(approx_eq(&a, &b));
+    }
+    #[test]
+    fn decompose() {
+        let t = Vec3::from(3.0, -2.0, 7.0);
+        let r = Vec3::from(deg(30.0), deg(45.0), deg(60.0));
+        let s = Vec3::from(2.0, 0.5, 4.0);
+
+        let mut m = Mat4::new();
+        m.translate(t);
+        m.rotate(r);
+        m.scale(s);
+
+        let (dt, dr, ds) = m.decompose();
+
+        assert!(approx_eq_vec3(dt, t));
+        assert!(approx_eq_vec3(
+            Vec3::from(dr.x.degrees(), dr.y.degrees(), dr.z.degrees()),
+            Vec3::from(30.0, 45.0, 60.0)));
+        assert!(approx_eq_vec3(ds, s));
+
+        let mut rebuilt = Mat4::new();
+        rebuilt.translate(dt);
+        rebuilt.rotate(dr);
+        rebuilt.scale(ds);
+        assert!(approx_eq(&rebuilt, &m));
+    }
+    #[test]
+    fn decompose_gimbal_lock() {
+        let mut m = Mat4::new();
+        m.rotate(Vec3::from(deg(90.0), deg(30.0), deg(0.0)));
+        m.scale(Vec3::from(1.0, 2.0, 3.0));
+
+        let (_, dr, ds) = m.decompose();
+
+        assert!(approx_eq_vec3(ds, Vec3::from(1.0, 2.0, 3.0)));
+
+        let mut rebuilt = Mat4::new();
+        rebuilt.rotate(dr);
+        rebuilt.scale(ds);
+        assert!(approx_eq(&rebuilt, &m));
+    }
+    #[test]
+    fn perspective() {
+        let mut m = Mat4::new();
+        m.set_perspective(deg(90.0), 2.0, 1.0, 100.0);
+
+        let arr = m.as_array();
+
+        // tan(45 degrees) is 1
+        assert!((arr[5] - 1.0).abs() < 0.00001);
+        assert!((arr[0] - 0.5).abs() < 0.00001);
+    }
+    #[test]
+    fn perspective_reference() {
+        let m = Mat4::perspective_rh_gl(deg(90.0), 2.0, 1.0, 100.0);
+
+        let expected = Mat4::from_array([
+            0.5, 0.0,           0.0,  0.0,
+            0.0, 1.0,           0.0,  0.0,
+            0.0, 0.0,  -101.0/99.0, -1.0,
+            0.0, 0.0,  -200.0/99.0,  0.0
+        ]);
+        assert!(approx_eq(&m, &expected));
+
+        // near and far planes land on the ends of the depth range
+        let depth = |m: Mat4, z: f32| m.transform_point(Vec3::from(0.0, 0.0, z)).z;
+
+        let m = Mat4::perspective_rh_gl(deg(60.0), 1.5, 0.5, 50.0);
+        assert!((depth(m,  -0.5) - -1.0).abs() < 0.0001);
+        assert!((depth(m, -50.0) -  1.0).abs() < 0.0001);
+
+        let m = Mat4::perspective_rh_zo(deg(60.0), 1.5, 0.5, 50.0);
+        assert!((depth(m,  -0.5) - 0.0).abs() < 0.0001);
+        assert!((depth(m, -50.0) - 1.0).abs() < 0.0001);
+
+        let m = Mat4::perspective_lh_gl(deg(60.0), 1.5, 0.5, 50.0);
+        assert!((depth(m,  0.5) - -1.0).abs() < 0.0001);
+        assert!((depth(m, 50.0) -  1.0).abs() < 0.0001);
+
+        let m = Mat4::perspective_lh_zo(deg(60.0), 1.5, 0.5, 50.0);
+        assert!((depth(m,  0.5) - 0.0).abs() < 0.0001);
+        assert!((depth(m, 50.0) - 1.0).abs() < 0.0001);
+
+        let m = Mat4::perspective_infinite_reverse_z(deg(60.0), 1.5, 0.5);
+        assert!((depth(m, -0.5) - 1.0).abs() < 0.0001);
+        assert!(depth(m, -1000000.0) < 0.000001);
+        assert!(depth(m, -10.0) > depth(m, -20.0));
+    }
+    #[test]
+    fn orthographic_reference() {
+        let m = Mat4::orthographic_rh_gl(-2.0, 2.0, -1.0, 1.0, 1.0, 11.0);
+
+        let expected = Mat4::from_array([
+            0.5, 0.0,  0.0, 0.0,
+            0.0, 1.0,  0.0, 0.0,
+            0.0, 0.0, -0.2, 0.0,
+            0.0, 0.0, -1.2, 1.0
+        ]);
+        assert!(approx_eq(&m, &expected));
+
+        let corners = |m: Mat4, z_near: f32, z_far: f32| {
+            (m.transform_point(Vec3::from(-2.0, -1.0, z_near)),
+             m.transform_point(Vec3::from( 2.0,  1.0, z_far)))
+        };
+
+        let (a, b) = corners(m, -1.0, -11.0);
+        assert!(approx_eq_vec3(a, Vec3::from(-1.0, -1.0, -1.0)));
+        assert!(approx_eq_vec3(b, Vec3::from( 1.0,  1.0,  1.0)));
+
+        let m = Mat4::orthographic_rh_zo(-2.0, 2.0, -1.0, 1.0, 1.0, 11.0);
+        let (a, b) = corners(m, -1.0, -11.0);
+        assert!(approx_eq_vec3(a, Vec3::from(-1.0, -1.0, 0.0)));
+        assert!(approx_eq_vec3(b, Vec3::from( 1.0,  1.0, 1.0)));
+
+        let m = Mat4::orthographic_lh_gl(-2.0, 2.0, -1.0, 1.0, 1.0, 11.0);
+        let (a, b) = corners(m, 1.0, 11.0);
+        assert!(approx_eq_vec3(a, Vec3::from(-1.0, -1.0, -1.0)));
+        assert!(approx_eq_vec3(b, Vec3::from( 1.0,  1.0,  1.0)));
+
+        let m = Mat4::orthographic_lh_zo(-2.0, 2.0, -1.0, 1.0, 1.0, 11.0);
+        let (a, b) = corners(m, 1.0, 11.0);
+        assert!(approx_eq_vec3(a, Vec3::from(-1.0, -1.0, 0.0)));
+        assert!(approx_eq_vec3(b, Vec3::from( 1.0,  1.0, 1.0)));
+    }
+    #[test]
+    fn look_at() {
+        let up = Vec3::from(0.0, 1.0, 0.0);
+
+        let m = Mat4::look_at_rh(Vec3::from(0.0, 0.0, 5.0), Vec3::zero(), up);
+        assert!(approx_eq_vec3(m.transform_point(Vec3::zero()),
+                               Vec3::from(0.0, 0.0, -5.0)));
+        assert!(approx_eq_vec3(m.transform_point(Vec3::from(1.0, 2.0, 0.0)),
+                               Vec3::from(1.0, 2.0, -5.0)));
+
+        let m = Mat4::look_at_lh(Vec3::from(0.0, 0.0, -5.0), Vec3::zero(), up);
+        assert!(approx_eq_vec3(m.transform_point(Vec3::zero()),
+                               Vec3::from(0.0, 0.0, 5.0)));
+        assert!(approx_eq_vec3(m.transform_point(Vec3::from(1.0, 2.0, 0.0)),
+                               Vec3::from(1.0, 2.0, 5.0)));
+
+        // looking down +x from the origin
+        let m = Mat4::look_at_rh(Vec3::zero(), Vec3::from(1.0, 0.0, 0.0), up);
+        assert!(approx_eq_vec3(m.transform_point(Vec3::from(3.0, 0.0, 1.0)),
+                               Vec3::from(1.0, 0.0, -3.0)));
+    }
+    #[test]
+    fn set_projection_replaces_all() {
+        let mut m = Mat4::new();
+        m.translate(Vec3::from(1.0, 2.0, 3.0));
+        m.rotate_y(deg(30.0));
+
+        m.set_perspective(deg(60.0), 1.5, 0.5, 50.0);
+        assert!(m == Mat4::perspective_lh_zo(deg(60.0), 1.5, 0.5, 50.0));
+
+        m.set_orthographic(-2.0, 2.0, -1.0, 1.0, 1.0, 11.0);
+        assert!(m == Mat4::orthographic_lh_zo(-2.0, 2.0, -1.0, 1.0, 1.0, 11.0));
+    }
 }
@@ -0,0 +1,336 @@
+extern crate num;
+use self::num::Num;
+use std::ops::{Index, IndexMut, Mul, MulAssign, Add, AddAssign, Sub, SubAssign,
+               Neg};
+
+use super::mat2::Mat2;
+use super::mat3::Mat3;
+use super::mat4::Mat4;
+
+/// A generic matrix of `R` rows and `C` columns of any numeric type
+///
+/// The data is stored column major, in the same order as
+/// [`Mat4`](struct.Mat4.html), and elements are indexed by `(row, column)`.
+/// Square f32 matrices convert to and from [`Mat2`](struct.Mat2.html),
+/// [`Mat3`](struct.Mat3.html) and Mat4, which provide the transformation
+/// constructors and mutators.
+///
+/// `a * b` is the standard matrix product, so the number of columns of `a`
+/// must match the number of rows of `b`. This is the reverse of Mat4, where
+/// `a * b` applies a and then b: `Mat4::from(Matrix::from(b)*Matrix::from(a))`
+/// equals `a * b`.
+#[derive(Copy,Clone,PartialEq,Debug)]
+pub struct Matrix<T, const R: usize, const C: usize> {
+    data: [[T; R]; C]
+}
+
+/// Matrix of f32
+pub type Matrixf<const R: usize, const C: usize> = Matrix<f32, R, C>;
+/// Matrix of f64
+pub type Matrixd<const R: usize, const C: usize> = Matrix<f64, R, C>;
+
+/// Construct methods for Matrix
+impl<T, const R: usize, const C: usize> Matrix<T, R, C> where T: Num + Copy {
+    /// Constructs a new Matrix with every element set to 0
+    pub fn zero() -> Self {
+        Matrix {
+            data: [[T::zero(); R]; C]
+        }
+    }
+    /// Constructs a new Matrix from its columns
+    pub fn from_columns(columns: [[T; R]; C]) -> Self {
+        Matrix {
+            data: columns
+        }
+    }
+    /// Constructs a new Matrix from its rows
+    pub fn from_rows(rows: [[T; C]; R]) -> Self {
+        Matrix::<T, C, R>::from_columns(rows).transpose()
+    }
+}
+
+/// Construct methods for square Matrix
+impl<T, const N: usize> Matrix<T, N, N> where T: Num + Copy {
+    /// Constructs a new Matrix and sets it to the identity matrix
+    pub fn identity() -> Self {
+        let mut m = Self::zero();
+        for i in 0..N {
+            m.data[i][i] = T::one();
+        }
+        m
+    }
+}
+
+/// Get methods for Matrix
+impl<T, const R: usize, const C: usize> Matrix<T, R, C> where T: Copy {
+    /// Returns the number of rows
+    pub fn rows(&self) -> usize {
+        R
+    }
+    /// Returns the number of columns
+    pub fn columns(&self) -> usize {
+        C
+    }
+    /// Returns the element at `row` and `column`
+    pub fn get(&self, row: usize, column: usize) -> T {
+        self.data[column][row]
+    }
+    /// Returns the elements of row `i`
+    pub fn row(&self, i: usize) -> [T; C] {
+        let mut row = [self.data[0][i]; C];
+        for (c, e) in row.iter_mut().enumerate() {
+            *e = self.data[c][i];
+        }
+        row
+    }
+    /// Returns the elements of column `i`
+    pub fn column(&self, i: usize) -> [T; R] {
+        self.data[i]
+    }
+    /// Returns the columns of the Matrix
+    pub fn as_columns(&self) -> [[T; R]; C] {
+        self.data
+    }
+}
+
+/// Calc methods for Matrix
+impl<T, const R: usize, const C: usize> Matrix<T, R, C> where T: Num + Copy {
+    /// Returns a new Matrix with rows and columns swapped
+    pub fn transpose(&self) -> Matrix<T, C, R> {
+        let mut m = Matrix::<T, C, R>::zero();
+        for c in 0..C {
+            for r in 0..R {
+                m.data[r][c] = self.data[c][r];
+            }
+        }
+        m
+    }
+}
+
+/// Mutate methods for Matrix
+impl<T, const R: usize, const C: usize> Matrix<T, R, C> where T: Copy {
+    /// Sets the element at `row` and `column` to `value`
+    pub fn set(&mut self, row: usize, column: usize, value: T) {
+        self.data[column][row] = value;
+    }
+}
+
+impl<T, const R: usize, const C: usize> Index<(usize, usize)>
+    for Matrix<T, R, C> {
+    type Output = T;
+
+    fn index(&self, (row, column): (usize, usize)) -> &T {
+        &self.data[column][row]
+    }
+}
+impl<T, const R: usize, const C: usize> IndexMut<(usize, usize)>
+    for Matrix<T, R, C> {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut T {
+        &mut self.data[column][row]
+    }
+}
+impl<T, const R: usize, const C: usize, const K: usize> Mul<Matrix<T, C, K>>
+    for Matrix<T, R, C>
+    where T: Num + Copy {
+    type Output = Matrix<T, R, K>;
+
+    fn mul(self, rhs: Matrix<T, C, K>) -> Matrix<T, R, K> {
+        let mut m = Matrix::<T, R, K>::zero();
+        for k in 0..K {
+            for r in 0..R {
+                let mut sum = T::zero();
+                for i in 0..C {
+                    sum = sum + self.data[i][r]*rhs.data[k][i];
+                }
+                m.data[k][r] = sum;
+            }
+        }
+        m
+    }
+}
+impl<T, const R: usize, const C: usize> Mul<T> for Matrix<T, R, C>
+    where T: Num + Copy {
+    type Output = Self;
+
+    fn mul(mut self, rhs: T) -> Self {
+        self *= rhs;
+        self
+    }
+}
+impl<T, const R: usize, const C: usize> MulAssign<T> for Matrix<T, R, C>
+    where T: Num + Copy {
+    fn mul_assign(&mut self, rhs: T) {
+        for e in self.data.iter_mut().flat_map(|c| c.iter_mut()) {
+            *e = *e*rhs;
+        }
+    }
+}
+impl<T, const R: usize, const C: usize> Add for Matrix<T, R, C>
+    where T: Num + Copy {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        self += rhs;
+        self
+    }
+}
+impl<T, const R: usize, const C: usize> AddAssign for Matrix<T, R, C>
+    where T: Num + Copy {
+    fn add_assign(&mut self, rhs: Self) {
+        for c in 0..C {
+            for r in 0..R {
+                self.data[c][r] = self.data[c][r] + rhs.data[c][r];
+            }
+        }
+    }
+}
+impl<T, const R: usize, const C: usize> Sub for Matrix<T, R, C>
+    where T: Num + Copy {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self {
+        self -= rhs;
+        self
+    }
+}
+impl<T, const R: usize, const C: usize> SubAssign for Matrix<T, R, C>
+    where T: Num + Copy {
+    fn sub_assign(&mut self, rhs: Self) {
+        for c in 0..C {
+            for r in 0..R {
+                self.data[c][r] = self.data[c][r] - rhs.data[c][r];
+            }
+        }
+    }
+}
+impl<T, const R: usize, const C: usize> Neg for Matrix<T, R, C>
+    where T: Num + Neg<Output = T> + Copy {
+    type Output = Self;
+
+    fn neg(mut self) -> Self {
+        for e in self.data.iter_mut().flat_map(|c| c.iter_mut()) {
+            *e = -*e;
+        }
+        self
+    }
+}
+impl<T, const N: usize> Default for Matrix<T, N, N> where T: Num + Copy {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl From<Mat2> for Matrix<f32, 2, 2> {
+    fn from(m: Mat2) -> Self {
+        let m = m.as_array();
+        Matrix::from_columns([[m[0], m[1]],
+                              [m[2], m[3]]])
+    }
+}
+impl From<Matrix<f32, 2, 2>> for Mat2 {
+    fn from(m: Matrix<f32, 2, 2>) -> Self {
+        let [a, b] = m.data;
+        Mat2::from_array([a[0], a[1],
+                          b[0], b[1]])
+    }
+}
+impl From<Mat3> for Matrix<f32, 3, 3> {
+    fn from(m: Mat3) -> Self {
+        let m = m.as_array();
+        Matrix::from_columns([[m[0], m[1], m[2]],
+                              [m[3], m[4], m[5]],
+                              [m[6], m[7], m[8]]])
+    }
+}
+impl From<Matrix<f32, 3, 3>> for Mat3 {
+    fn from(m: Matrix<f32, 3, 3>) -> Self {
+        let [a, b, c] = m.data;
+        Mat3::from_array([a[0], a[1], a[2],
+                          b[0], b[1], b[2],
+                          c[0], c[1], c[2]])
+    }
+}
+impl From<Mat4> for Matrix<f32, 4, 4> {
+    fn from(m: Mat4) -> Self {
+        let m = m.as_array();
+        Matrix::from_columns([[m[ 0], m[ 1], m[ 2], m[ 3]],
+                              [m[ 4], m[ 5], m[ 6], m[ 7]],
+                              [m[ 8], m[ 9], m[10], m[11]],
+                              [m[12], m[13], m[14], m[15]]])
+    }
+}
+impl From<Matrix<f32, 4, 4>> for Mat4 {
+    fn from(m: Matrix<f32, 4, 4>) -> Self {
+        let [a, b, c, d] = m.data;
+        Mat4::from_array([a[0], a[1], a[2], a[3],
+                          b[0], b[1], b[2], b[3],
+                          c[0], c[1], c[2], c[3],
+                          d[0], d[1], d[2], d[3]])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Matrix;
+    use super::super::mat3::Mat3;
+    use super::super::mat4::Mat4;
+    use super::super::vec2::Vec2;
+    use super::super::vec3::Vec3;
+    use super::super::simple::deg;
+
+    #[test]
+    fn layout() {
+        let m = Matrix::from_rows([[1, 2, 3],
+                                   [4, 5, 6]]);
+
+        assert_eq!(m.rows(), 2);
+        assert_eq!(m.columns(), 3);
+        assert_eq!(m.get(0, 2), 3);
+        assert_eq!(m[(1, 0)], 4);
+        assert_eq!(m.row(1), [4, 5, 6]);
+        assert_eq!(m.column(1), [2, 5]);
+        assert_eq!(m.transpose(), Matrix::from_columns([[1, 2, 3], [4, 5, 6]]));
+        assert_eq!(m.transpose().transpose(), m);
+    }
+    #[test]
+    fn arithmetic() {
+        let a = Matrix::from_rows([[1, 2], [3, 4]]);
+        let b = Matrix::from_rows([[5, 6], [7, 8]]);
+
+        assert_eq!(a + b, Matrix::from_rows([[6, 8], [10, 12]]));
+        assert_eq!(b - a, Matrix::from_rows([[4, 4], [4, 4]]));
+        assert_eq!(a*2, a + a);
+        assert_eq!(-a + a, Matrix::zero());
+        assert_eq!(a*Matrix::identity(), a);
+
+        assert_eq!(a*b, Matrix::from_rows([[19, 22], [43, 50]]));
+        assert_eq!(b*a, Matrix::from_rows([[23, 34], [31, 46]]));
+
+        // a 2x3 times a 3x1 gives a 2x1
+        let c = Matrix::from_rows([[1, 0, 2],
+                                   [0, 1, 3]]);
+        let d = Matrix::from_rows([[2], [-1], [1]]);
+        assert_eq!(c*d, Matrix::from_rows([[4], [2]]));
+    }
+    #[test]
+    fn conversions() {
+        let mut m = Mat4::new();
+        m.translate(Vec3::from(1.0, 2.0, 3.0));
+        m.rotate_y(deg(30.0));
+
+        let g = Matrix::from(m);
+        assert_eq!(g.get(0, 3), 1.0);
+        assert_eq!(Mat4::from(g), m);
+        assert_eq!(Mat4::from(g*g), m*m);
+
+        let mut t = Mat4::new();
+        t.translate(Vec3::from(0.0, -1.0, 4.0));
+        assert_eq!(Mat4::from(Matrix::from(t)*g), m*t);
+
+        let mut n = Mat3::new();
+        n.translate(Vec2::from(4.0, 5.0));
+        n.rotate(deg(45.0));
+        assert_eq!(Mat3::from(Matrix::from(n)), n);
+        assert_eq!(Mat3::from(Matrix::from(n)*Matrix::from(n)), n*n);
+    }
+}
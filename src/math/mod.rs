@@ -1,15 +1,36 @@
 //! Math module providing linear algebra structures
-//mod angle;
-//pub use self::angle::Angle;
+mod angle;
+pub use self::angle::Angle;
 mod vec2;
-pub use self::vec2::Vec2;
+pub use self::vec2::{Vec2, Vec2f, Vec2d};
 mod vec3;
-pub use self::vec3::Vec3;
+pub use self::vec3::{Vec3, Vec3f, Vec3d};
 mod vec4;
-pub use self::vec4::Vec4;
-//mod mat4;
-//pub use self::mat4::Mat4;
+pub use self::vec4::{Vec4, Vec4f, Vec4d};
+mod mat2;
+pub use self::mat2::Mat2;
+mod mat3;
+pub use self::mat3::Mat3;
+mod mat4;
+pub use self::mat4::Mat4;
+mod matrix;
+pub use self::matrix::{Matrix, Matrixf, Matrixd};
+mod quat;
+pub use self::quat::Quat;
+mod transform;
+pub use self::transform::Transform;
+mod aabb;
+pub use self::aabb::{Aabb2, Aabb3};
+mod sphere;
+pub use self::sphere::BoundingSphere;
+mod plane;
+pub use self::plane::Plane;
+mod ray;
+pub use self::ray::Ray3;
+mod frustum;
+pub use self::frustum::{Frustum, Containment};
 
-mod simple {
-    //pub use super::angle::{rad, deg};
+/// Short constructor functions, meant to be glob imported
+pub mod simple {
+    pub use super::angle::{rad, deg};
 }
@@ -0,0 +1,85 @@
+use super::vec3::Vec3;
+
+/// An infinite plane, given by its normal and distance
+///
+/// The points `p` on the plane are those where
+/// `normal.dot(&p) + distance == 0`, so `distance` is how far the origin is
+/// in front of the plane when the normal has a length of 1.
+#[derive(Copy,Clone,PartialEq,Debug)]
+pub struct Plane {
+    /// The direction the front of the plane faces
+    pub normal: Vec3<f32>,
+    /// The signed distance from the plane to the origin
+    pub distance: f32
+}
+
+/// Construct methods for Plane
+impl Plane {
+    /// Constructs a new Plane from `normal` and `distance`
+    pub fn new(normal: Vec3<f32>, distance: f32) -> Self {
+        Plane {
+            normal,
+            distance
+        }
+    }
+    /// Constructs a new Plane through `point` facing `normal`
+    pub fn from_point_normal(point: Vec3<f32>, normal: Vec3<f32>) -> Self {
+        let normal = normal.normalize_or_zero();
+        Plane::new(normal, -normal.dot(&point))
+    }
+    /// Constructs a new Plane through the points `a`, `b` and `c`
+    ///
+    /// The front of the plane is the side the points wind counter-clockwise
+    /// on.
+    pub fn from_points(a: Vec3<f32>, b: Vec3<f32>, c: Vec3<f32>) -> Self {
+        Self::from_point_normal(a, (b - a).cross(c - a))
+    }
+}
+
+/// Calc methods for Plane
+impl Plane {
+    /// Returns a new Plane with a normal of length 1, describing the same
+    /// points
+    pub fn normalized(&self) -> Self {
+        let len = self.normal.length();
+        if len == 0.0 {
+            return *self;
+        }
+
+        Plane::new(self.normal/len, self.distance/len)
+    }
+    /// Calculates the signed distance from the Plane to `p`
+    ///
+    /// The result is positive in front of the plane and negative behind it,
+    /// and is only in world units if the normal has a length of 1.
+    pub fn signed_distance(&self, p: Vec3<f32>) -> f32 {
+        self.normal.dot(&p) + self.distance
+    }
+    /// Returns the point on the Plane closest to `p`
+    pub fn project_point(&self, p: Vec3<f32>) -> Vec3<f32> {
+        let n = self.normalized();
+        p - n.normal*n.signed_distance(p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Plane;
+    use super::super::vec3::Vec3;
+
+    #[test]
+    fn from_points() {
+        let p = Plane::from_points(Vec3::from(0.0, 2.0, 0.0),
+                                   Vec3::from(0.0, 2.0, 1.0),
+                                   Vec3::from(1.0, 2.0, 0.0));
+
+        assert_eq!(p, Plane::new(Vec3::from(0.0, 1.0, 0.0), -2.0));
+        assert_eq!(p.signed_distance(Vec3::from(5.0, 3.0, 5.0)), 1.0);
+        assert_eq!(p.signed_distance(Vec3::zero()), -2.0);
+        assert_eq!(p.project_point(Vec3::from(1.0, 7.0, 3.0)),
+                   Vec3::from(1.0, 2.0, 3.0));
+
+        let n = Plane::new(Vec3::from(0.0, 0.0, 2.0), 4.0).normalized();
+        assert_eq!(n, Plane::new(Vec3::from(0.0, 0.0, 1.0), 2.0));
+    }
+}
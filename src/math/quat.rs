@@ -0,0 +1,387 @@
+use std::ops::{Mul, MulAssign, Neg};
+
+use super::angle::Angle;
+use super::vec3::Vec3;
+use super::mat4::Mat4;
+
+/// A quaternion for representing rotations
+///
+/// Unlike Euler angles a Quat doesn't suffer from gimbal lock and can be
+/// smoothly interpolated with [`slerp`](#method.slerp). Quats used for
+/// rotation should be kept at unit length.
+#[derive(Copy,Clone,PartialEq,Debug)]
+pub struct Quat {
+    /// The x element of the vector part
+    pub x: f32,
+    /// The y element of the vector part
+    pub y: f32,
+    /// The z element of the vector part
+    pub z: f32,
+    /// The scalar part
+    pub w: f32
+}
+
+/// Construct methods for Quat
+impl Quat {
+    /// Constructs a new Quat with no rotation
+    pub fn identity() -> Self {
+        Quat::from(0.0, 0.0, 0.0, 1.0)
+    }
+    /// Constructs a new Quat and sets it to identity
+    pub fn new() -> Self {
+        Self::identity()
+    }
+    /// Constructs a new Quat from values `x`, `y`, `z` and `w`
+    pub fn from(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Quat {
+            x,
+            y,
+            z,
+            w
+        }
+    }
+    /// Constructs a new Quat rotating around `axis` by `angle`
+    pub fn from_axis_angle(axis: Vec3<f32>, angle: Angle<f32>) -> Self {
+        let axis = axis.normalized();
+        let (s, c) = (angle*0.5).sin_cos();
+
+        Quat::from(axis.x*s, axis.y*s, axis.z*s, c)
+    }
+    /// Constructs a new Quat from Euler angles
+    ///
+    /// The rotation is the same as the one built by
+    /// [`Mat4::rotate`](struct.Mat4.html#method.rotate).
+    pub fn from_euler(v: Vec3<Angle<f32>>) -> Self {
+        Quat::from_axis_angle(Vec3::from(0.0, 0.0, 1.0),  v.z)
+            * Quat::from_axis_angle(Vec3::from(1.0, 0.0, 0.0),  v.x)
+            * Quat::from_axis_angle(Vec3::from(0.0, 1.0, 0.0), -v.y)
+    }
+    /// Constructs a new Quat with the shortest rotation turning direction
+    /// `from` into direction `to`
+    pub fn from_to_rotation(from: Vec3<f32>, to: Vec3<f32>) -> Self {
+        let a = from.normalized();
+        let b = to.normalized();
+
+        let d = a.dot(&b);
+
+        if d >= 1.0 - 0.000001 {
+            return Quat::identity();
+        }
+        if d <= -1.0 + 0.000001 {
+            // opposite directions; turn half way around any perpendicular axis
+            let mut axis = Vec3::right().cross(a);
+            if axis.length_squared() < 0.000001 {
+                axis = Vec3::up().cross(a);
+            }
+            return Quat::from_axis_angle(axis, Angle::Degrees(180.0));
+        }
+
+        let c = a.cross(b);
+        let s = ((1.0 + d)*2.0).sqrt();
+
+        Quat::from(c.x/s, c.y/s, c.z/s, s*0.5)
+    }
+    /// Constructs a new Quat from the rotation of a Mat4
+    ///
+    /// Translation is ignored and scale is removed from the rotation.
+    pub fn from_mat4(m: &Mat4) -> Self {
+        let m = m.as_array();
+
+        let sx = Vec3::from(m[0], m[1], m[ 2]).length();
+        let sy = Vec3::from(m[4], m[5], m[ 6]).length();
+        let sz = Vec3::from(m[8], m[9], m[10]).length();
+
+        // rotation matrix elements r[row][column]
+        let (r00, r10, r20) = (m[0]/sx, m[1]/sx, m[ 2]/sx);
+        let (r01, r11, r21) = (m[4]/sy, m[5]/sy, m[ 6]/sy);
+        let (r02, r12, r22) = (m[8]/sz, m[9]/sz, m[10]/sz);
+
+        let trace = r00 + r11 + r22;
+
+        let q = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt()*2.0;
+            Quat::from((r21 - r12)/s, (r02 - r20)/s, (r10 - r01)/s, 0.25*s)
+        } else if r00 > r11 && r00 > r22 {
+            let s = (1.0 + r00 - r11 - r22).sqrt()*2.0;
+            Quat::from(0.25*s, (r01 + r10)/s, (r02 + r20)/s, (r21 - r12)/s)
+        } else if r11 > r22 {
+            let s = (1.0 + r11 - r00 - r22).sqrt()*2.0;
+            Quat::from((r01 + r10)/s, 0.25*s, (r12 + r21)/s, (r02 - r20)/s)
+        } else {
+            let s = (1.0 + r22 - r00 - r11).sqrt()*2.0;
+            Quat::from((r02 + r20)/s, (r12 + r21)/s, 0.25*s, (r10 - r01)/s)
+        };
+
+        q.normalized()
+    }
+}
+
+impl Default for Quat {
+    fn default() -> Self {
+        Quat::new()
+    }
+}
+
+/// Calc methods for Quat
+impl Quat {
+    /// Calculates the dot product of two Quats
+    pub fn dot(&self, b: &Self) -> f32 {
+        self.x*b.x + self.y*b.y + self.z*b.z + self.w*b.w
+    }
+    /// Calculates the length squared of a Quat
+    pub fn length_squared(&self) -> f32 {
+        self.dot(self)
+    }
+    /// Calculates the length of a Quat
+    pub fn length(&self) -> f32 {
+        self.length_squared().sqrt()
+    }
+    /// Returns a new Quat with values normalized (unit length)
+    pub fn normalized(&self) -> Self {
+        let len = self.length();
+        Quat::from(self.x/len, self.y/len, self.z/len, self.w/len)
+    }
+    /// Mutates self to become normalized (unit length)
+    pub fn normalize_self(&mut self) {
+        *self = self.normalized();
+    }
+    /// Returns the conjugate of a Quat (the vector part negated)
+    ///
+    /// For unit Quats this is the same as the [`inverse`](#method.inverse),
+    /// but cheaper to calculate.
+    pub fn conjugate(&self) -> Self {
+        Quat::from(-self.x, -self.y, -self.z, self.w)
+    }
+    /// Returns the inverse of a Quat (the opposite rotation)
+    pub fn inverse(&self) -> Self {
+        let len_sq = self.length_squared();
+        let c = self.conjugate();
+        Quat::from(c.x/len_sq, c.y/len_sq, c.z/len_sq, c.w/len_sq)
+    }
+    /// Normalized linear interpolation from self to `b` by `t` (0 to 1)
+    ///
+    /// Cheaper than [`slerp`](#method.slerp) but doesn't rotate at a constant
+    /// speed.
+    pub fn nlerp(&self, b: &Self, t: f32) -> Self {
+        let b = if self.dot(b) < 0.0 { -*b } else { *b };
+
+        Quat::from(
+            self.x + (b.x - self.x)*t,
+            self.y + (b.y - self.y)*t,
+            self.z + (b.z - self.z)*t,
+            self.w + (b.w - self.w)*t
+        ).normalized()
+    }
+    /// Spherical linear interpolation from self to `b` by `t` (0 to 1)
+    ///
+    /// Always takes the shortest path between the two rotations.
+    pub fn slerp(&self, b: &Self, t: f32) -> Self {
+        let mut d = self.dot(b);
+        let b = if d < 0.0 {
+            d = -d;
+            -*b
+        } else {
+            *b
+        };
+
+        // nearly the same rotation; sin(theta) approaches zero
+        if d > 0.9995 {
+            return self.nlerp(&b, t);
+        }
+
+        let theta = d.acos();
+        let sin_theta = theta.sin();
+
+        let sa = ((1.0 - t)*theta).sin()/sin_theta;
+        let sb = (t*theta).sin()/sin_theta;
+
+        Quat::from(
+            self.x*sa + b.x*sb,
+            self.y*sa + b.y*sb,
+            self.z*sa + b.z*sb,
+            self.w*sa + b.w*sb
+        )
+    }
+    /// Returns the rotation of a Quat as a Mat4
+    pub fn to_mat4(&self) -> Mat4 {
+        let (x, y, z, w) = (self.x, self.y, self.z, self.w);
+
+        let (xx, yy, zz) = (x*x, y*y, z*z);
+        let (xy, xz, yz) = (x*y, x*z, y*z);
+        let (wx, wy, wz) = (w*x, w*y, w*z);
+
+        Mat4::from_array([
+            1.0 - 2.0*(yy + zz), 2.0*(xy + wz),       2.0*(xz - wy),       0.0,
+            2.0*(xy - wz),       1.0 - 2.0*(xx + zz), 2.0*(yz + wx),       0.0,
+            2.0*(xz + wy),       2.0*(yz - wx),       1.0 - 2.0*(xx + yy), 0.0,
+            0.0,                 0.0,                 0.0,                 1.0
+        ])
+    }
+}
+
+impl Neg for Quat {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Quat::from(-self.x, -self.y, -self.z, -self.w)
+    }
+}
+/// Hamilton product; `a * b` rotates by `b` first and then by `a`
+impl Mul<Self> for Quat {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Quat::from(
+            self.w*rhs.x + self.x*rhs.w + self.y*rhs.z - self.z*rhs.y,
+            self.w*rhs.y - self.x*rhs.z + self.y*rhs.w + self.z*rhs.x,
+            self.w*rhs.z + self.x*rhs.y - self.y*rhs.x + self.z*rhs.w,
+            self.w*rhs.w - self.x*rhs.x - self.y*rhs.y - self.z*rhs.z
+        )
+    }
+}
+impl MulAssign<Self> for Quat {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+/// Rotates a Vec3 by the Quat
+impl Mul<Vec3<f32>> for Quat {
+    type Output = Vec3<f32>;
+
+    fn mul(self, rhs: Vec3<f32>) -> Vec3<f32> {
+        let u = Vec3::from(self.x, self.y, self.z);
+        let t = u.cross(rhs)*2.0;
+
+        rhs + t*self.w + u.cross(t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Quat;
+    use super::super::vec3::Vec3;
+    use super::super::mat4::Mat4;
+    use super::super::simple::deg;
+
+    fn approx_eq(a: &Mat4, b: &Mat4) -> bool {
+        let (a, b) = (a.as_array(), b.as_array());
+        (0..16).all(|i| (a[i] - b[i]).abs() < 0.0001)
+    }
+    fn approx_eq_vec3(a: Vec3<f32>, b: Vec3<f32>) -> bool {
+        (a.x - b.x).abs() < 0.0001
+            && (a.y - b.y).abs() < 0.0001
+            && (a.z - b.z).abs() < 0.0001
+    }
+    fn same_rotation(a: &Quat, b: &Quat) -> bool {
+        // q and -q are the same rotation
+        (a.dot(b).abs() - 1.0).abs() < 0.0001
+    }
+
+    #[test]
+    fn axis_angle_rotates_vec3() {
+        let q = Quat::from_axis_angle(Vec3::from(0.0, 0.0, 1.0), deg(90.0));
+
+        let r = q * Vec3::from(1.0, 0.0, 0.0);
+
+        assert!(approx_eq_vec3(r, Vec3::from(0.0, 1.0, 0.0)));
+    }
+    #[test]
+    fn hamilton_product() {
+        let a = Quat::from_axis_angle(Vec3::from(0.0, 0.0, 1.0), deg(90.0));
+        let b = Quat::from_axis_angle(Vec3::from(1.0, 0.0, 0.0), deg(90.0));
+
+        // b first, then a
+        let v = Vec3::from(0.0, 1.0, 0.0);
+        assert!(approx_eq_vec3((a*b)*v, a*(b*v)));
+
+        // i*j = k
+        let k = Quat::from(1.0, 0.0, 0.0, 0.0)*Quat::from(0.0, 1.0, 0.0, 0.0);
+        assert!(k == Quat::from(0.0, 0.0, 1.0, 0.0));
+
+        let mut c = a;
+        c *= b;
+        assert!(c == a*b);
+    }
+    #[test]
+    fn conjugate_inverse() {
+        let q = Quat::from_euler(Vec3::from(deg(10.0), deg(20.0), deg(30.0)));
+        let v = Vec3::from(1.0, 2.0, 3.0);
+
+        assert!(approx_eq_vec3(q.conjugate()*(q*v), v));
+        assert!(same_rotation(&(q*q.inverse()), &Quat::identity()));
+
+        let scaled = Quat::from(q.x*2.0, q.y*2.0, q.z*2.0, q.w*2.0);
+        assert!(same_rotation(&(scaled*scaled.inverse()), &Quat::identity()));
+    }
+    #[test]
+    fn euler_matches_mat4() {
+        let r = Vec3::from(deg(30.0), deg(45.0), deg(60.0));
+
+        let mut m = Mat4::new();
+        m.rotate(r);
+
+        assert!(approx_eq(&Quat::from_euler(r).to_mat4(), &m));
+    }
+    #[test]
+    fn mat4_round_trip() {
+        let rotations = [
+            Vec3::from(deg(30.0), deg(45.0), deg(60.0)),
+            Vec3::from(deg(170.0), deg(0.0), deg(0.0)),
+            Vec3::from(deg(0.0), deg(170.0), deg(0.0)),
+            Vec3::from(deg(0.0), deg(0.0), deg(170.0)),
+            Vec3::from(deg(-120.0), deg(80.0), deg(10.0))
+        ];
+
+        for r in rotations.iter() {
+            let q = Quat::from_euler(*r);
+
+            let mut m = q.to_mat4();
+            m.scale(Vec3::from(2.0, 3.0, 4.0));
+            m.set_translation(Vec3::from(1.0, 2.0, 3.0));
+
+            assert!(same_rotation(&Quat::from_mat4(&m), &q));
+        }
+    }
+    #[test]
+    fn from_to_rotation() {
+        let a = Vec3::from(1.0, 0.0, 0.0);
+        let b = Vec3::from(0.0, 3.0, 4.0);
+
+        let q = Quat::from_to_rotation(a, b);
+        assert!(approx_eq_vec3(q*a, b.normalized()));
+
+        let q = Quat::from_to_rotation(a, a);
+        assert!(same_rotation(&q, &Quat::identity()));
+
+        let q = Quat::from_to_rotation(a, a*-1.0);
+        assert!(approx_eq_vec3(q*a, a*-1.0));
+    }
+    #[test]
+    fn slerp() {
+        let a = Quat::identity();
+        let b = Quat::from_axis_angle(Vec3::from(0.0, 1.0, 0.0), deg(90.0));
+
+        assert!(same_rotation(&a.slerp(&b, 0.0), &a));
+        assert!(same_rotation(&a.slerp(&b, 1.0), &b));
+
+        let half = a.slerp(&b, 0.5);
+        let expected = Quat::from_axis_angle(Vec3::from(0.0, 1.0, 0.0),
+                                             deg(45.0));
+        assert!(same_rotation(&half, &expected));
+
+        // shortest path is taken even when the signs disagree
+        assert!(same_rotation(&a.slerp(&-b, 0.5), &expected));
+    }
+    #[test]
+    fn nlerp() {
+        let a = Quat::identity();
+        let b = Quat::from_axis_angle(Vec3::from(1.0, 0.0, 0.0), deg(90.0));
+
+        let half = a.nlerp(&b, 0.5);
+        let expected = Quat::from_axis_angle(Vec3::from(1.0, 0.0, 0.0),
+                                             deg(45.0));
+
+        assert!((half.length() - 1.0).abs() < 0.0001);
+        assert!(same_rotation(&half, &expected));
+    }
+}
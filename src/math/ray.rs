@@ -0,0 +1,263 @@
+use std::f32;
+
+use super::vec2::Vec2;
+use super::vec3::Vec3;
+use super::mat4::Mat4;
+use super::plane::Plane;
+use super::aabb::Aabb3;
+use super::sphere::BoundingSphere;
+
+/// A half-infinite line starting at `origin` and heading along `direction`
+///
+/// Intersection methods return the distance `t` along the ray to the nearest
+/// hit, which can be turned back into a point with [`at`](#method.at). Hits
+/// behind the origin are ignored.
+#[derive(Copy,Clone,PartialEq,Debug)]
+pub struct Ray3 {
+    /// The point the ray starts at
+    pub origin: Vec3<f32>,
+    /// The direction the ray heads in, with a length of 1
+    pub direction: Vec3<f32>
+}
+
+/// Construct methods for Ray3
+impl Ray3 {
+    /// Constructs a new Ray3 from `origin` heading along `direction`
+    ///
+    /// `direction` is normalized, so distances along the ray are in world
+    /// units.
+    pub fn new(origin: Vec3<f32>, direction: Vec3<f32>) -> Self {
+        Ray3 {
+            origin,
+            direction: direction.normalize_or_zero()
+        }
+    }
+    /// Constructs a new Ray3 starting at `from` and heading through `to`
+    pub fn between(from: Vec3<f32>, to: Vec3<f32>) -> Self {
+        Ray3::new(from, to - from)
+    }
+    /// Constructs a new Ray3 through the pixel at `point` on a screen or
+    /// viewport of `size`, unprojected by `inverse_view_projection`
+    ///
+    /// `point` is in pixels from the top left corner. `ndc_near` is the clip
+    /// space depth of the near plane: -1 for the `gl` projections, 0 for the
+    /// `zo` projections and 1 for reverse-z. The ray starts on the near plane.
+    pub fn from_screen(point: Vec2<f32>, size: Vec2<f32>,
+                       inverse_view_projection: &Mat4, ndc_near: f32)
+                       -> Self {
+        let x = point.x/size.x*2.0 - 1.0;
+        let y = 1.0 - point.y/size.y*2.0;
+
+        // a second depth between the near and far planes, which is finite
+        // even for infinite projections
+        let ndc_far = if ndc_near < 1.0 { 1.0 } else { 0.0 };
+        let ndc_mid = (ndc_near + ndc_far)*0.5;
+
+        let m = inverse_view_projection;
+        Ray3::between(m.transform_point(Vec3::from(x, y, ndc_near)),
+                      m.transform_point(Vec3::from(x, y, ndc_mid)))
+    }
+}
+
+/// Calc methods for Ray3
+impl Ray3 {
+    /// Returns the point `t` along the Ray3
+    pub fn at(&self, t: f32) -> Vec3<f32> {
+        self.origin + self.direction*t
+    }
+    /// Returns the point on the Ray3 closest to `p`
+    pub fn closest_point(&self, p: Vec3<f32>) -> Vec3<f32> {
+        self.at((p - self.origin).dot(&self.direction).max(0.0))
+    }
+    /// Returns the distance along the Ray3 to `plane`, or `None` if the ray
+    /// is parallel to or heading away from it
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<f32> {
+        let denom = plane.normal.dot(&self.direction);
+        if denom == 0.0 {
+            return None;
+        }
+
+        let t = -plane.signed_distance(self.origin)/denom;
+        if t >= 0.0 { Some(t) } else { None }
+    }
+    /// Returns the distance along the Ray3 to the surface of `sphere`, or
+    /// `None` if the ray misses it
+    ///
+    /// A ray starting inside the sphere hits it at 0.
+    pub fn intersect_sphere(&self, sphere: &BoundingSphere) -> Option<f32> {
+        if sphere.is_empty() {
+            return None;
+        }
+
+        let m = self.origin - sphere.center;
+        let b = m.dot(&self.direction);
+        let c = m.length_squared() - sphere.radius*sphere.radius;
+
+        let disc = b*b - c;
+        if disc < 0.0 {
+            return None;
+        }
+
+        let t = -b - disc.sqrt();
+        if t >= 0.0 {
+            Some(t)
+        } else if c <= 0.0 {
+            Some(0.0)
+        } else {
+            None
+        }
+    }
+    /// Returns the distance along the Ray3 to `b`, or `None` if the ray
+    /// misses it
+    ///
+    /// A ray starting inside the box hits it at 0.
+    pub fn intersect_aabb(&self, b: &Aabb3) -> Option<f32> {
+        let mut t_min = 0.0f32;
+        let mut t_max = f32::INFINITY;
+
+        for i in 0..3 {
+            let inv = 1.0/self.direction[i];
+            let t1 = (b.min[i] - self.origin[i])*inv;
+            let t2 = (b.max[i] - self.origin[i])*inv;
+
+            // NaN from a ray lying in a slab's edge is ignored by min/max
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+        }
+
+        if t_min <= t_max { Some(t_min) } else { None }
+    }
+    /// Returns the distance along the Ray3 to the triangle `a`, `b`, `c`, or
+    /// `None` if the ray misses it
+    ///
+    /// Both sides of the triangle are hit. Uses the Möller–Trumbore
+    /// algorithm.
+    pub fn intersect_triangle(&self, a: Vec3<f32>, b: Vec3<f32>, c: Vec3<f32>)
+                              -> Option<f32> {
+        let e1 = b - a;
+        let e2 = c - a;
+
+        let p = self.direction.cross(e2);
+        let det = e1.dot(&p);
+        if det.abs() < f32::EPSILON {
+            return None;
+        }
+        let inv_det = 1.0/det;
+
+        let s = self.origin - a;
+        let u = s.dot(&p)*inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+
+        let q = s.cross(e1);
+        let v = self.direction.dot(&q)*inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        let t = e2.dot(&q)*inv_det;
+        if t >= 0.0 { Some(t) } else { None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ray3;
+    use super::super::vec2::Vec2;
+    use super::super::vec3::Vec3;
+    use super::super::mat4::Mat4;
+    use super::super::plane::Plane;
+    use super::super::aabb::Aabb3;
+    use super::super::sphere::BoundingSphere;
+    use super::super::simple::deg;
+
+    fn approx_eq_vec3(a: Vec3<f32>, b: Vec3<f32>) -> bool {
+        (a - b).length() < 0.0001
+    }
+
+    #[test]
+    fn plane_and_sphere() {
+        let r = Ray3::new(Vec3::from(0.0, 5.0, 0.0), Vec3::from(0.0, -2.0, 0.0));
+        assert_eq!(r.direction, Vec3::from(0.0, -1.0, 0.0));
+
+        let ground = Plane::new(Vec3::from(0.0, 1.0, 0.0), 0.0);
+        assert_eq!(r.intersect_plane(&ground), Some(5.0));
+        assert_eq!(r.intersect_plane(&Plane::new(Vec3::from(0.0, 1.0, 0.0), -6.0)),
+                   None);
+        assert_eq!(r.intersect_plane(&Plane::new(Vec3::from(1.0, 0.0, 0.0), 0.0)),
+                   None);
+
+        let s = BoundingSphere::new(Vec3::from(0.0, 1.0, 0.0), 1.0);
+        assert_eq!(r.intersect_sphere(&s), Some(3.0));
+        assert_eq!(Ray3::new(Vec3::from(0.0, 1.0, 0.0), Vec3::up())
+                       .intersect_sphere(&s), Some(0.0));
+        assert_eq!(Ray3::new(Vec3::from(0.0, 3.0, 0.0), Vec3::up())
+                       .intersect_sphere(&s), None);
+        assert_eq!(Ray3::new(Vec3::from(2.0, 5.0, 0.0), Vec3::down())
+                       .intersect_sphere(&s), None);
+
+        assert!(approx_eq_vec3(r.closest_point(Vec3::from(3.0, 1.0, 0.0)),
+                               Vec3::from(0.0, 1.0, 0.0)));
+    }
+    #[test]
+    fn aabb() {
+        let b = Aabb3::new(Vec3::from(1.0, -1.0, -1.0), Vec3::from(3.0, 1.0, 1.0));
+
+        let r = Ray3::new(Vec3::zero(), Vec3::right());
+        assert_eq!(r.intersect_aabb(&b), Some(1.0));
+        assert_eq!(Ray3::new(Vec3::zero(), Vec3::left()).intersect_aabb(&b), None);
+        assert_eq!(Ray3::new(Vec3::from(2.0, 0.0, 0.0), Vec3::up())
+                       .intersect_aabb(&b), Some(0.0));
+
+        // diagonal, just missing and just hitting the top edge
+        let r = Ray3::between(Vec3::zero(), Vec3::from(1.0, 1.1, 0.0));
+        assert_eq!(r.intersect_aabb(&b), None);
+        let r = Ray3::between(Vec3::zero(), Vec3::from(1.0, 0.9, 0.0));
+        assert!(approx_eq_vec3(r.at(r.intersect_aabb(&b).unwrap()),
+                               Vec3::from(1.0, 0.9, 0.0)));
+    }
+    #[test]
+    fn triangle() {
+        let (a, b, c) = (Vec3::from(0.0, 0.0, -2.0),
+                         Vec3::from(2.0, 0.0, -2.0),
+                         Vec3::from(0.0, 2.0, -2.0));
+
+        let r = Ray3::new(Vec3::from(0.5, 0.5, 0.0), Vec3::backward());
+        assert_eq!(r.intersect_triangle(a, b, c), Some(2.0));
+        assert_eq!(r.intersect_triangle(a, c, b), Some(2.0));
+
+        let r = Ray3::new(Vec3::from(1.5, 1.5, 0.0), Vec3::backward());
+        assert_eq!(r.intersect_triangle(a, b, c), None);
+        let r = Ray3::new(Vec3::from(0.5, 0.5, -3.0), Vec3::backward());
+        assert_eq!(r.intersect_triangle(a, b, c), None);
+        let r = Ray3::new(Vec3::from(0.5, 0.5, 0.0), Vec3::right());
+        assert_eq!(r.intersect_triangle(a, b, c), None);
+    }
+    #[test]
+    fn from_screen() {
+        let size = Vec2::from(800.0, 600.0);
+        let view = Mat4::look_at_rh(Vec3::from(0.0, 0.0, 5.0), Vec3::zero(),
+                                    Vec3::up());
+
+        let projections = [
+            (Mat4::perspective_rh_gl(deg(90.0), 800.0/600.0, 1.0, 100.0), -1.0),
+            (Mat4::perspective_rh_zo(deg(90.0), 800.0/600.0, 1.0, 100.0), 0.0),
+            (Mat4::perspective_infinite_reverse_z(deg(90.0), 800.0/600.0, 1.0), 1.0)
+        ];
+
+        for &(proj, ndc_near) in projections.iter() {
+            let inv = (view*proj).inverse();
+
+            let r = Ray3::from_screen(Vec2::from(400.0, 300.0), size, &inv, ndc_near);
+            assert!(approx_eq_vec3(r.origin, Vec3::from(0.0, 0.0, 4.0)));
+            assert!(approx_eq_vec3(r.direction, Vec3::from(0.0, 0.0, -1.0)));
+
+            // top left corner of the screen
+            let r = Ray3::from_screen(Vec2::zero(), size, &inv, ndc_near);
+            assert!(approx_eq_vec3(r.origin, Vec3::from(-4.0/3.0, 1.0, 4.0)));
+            assert!(approx_eq_vec3(r.direction,
+                                   Vec3::from(-4.0/3.0, 1.0, -1.0).normalized()));
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use super::vec3::Vec3;
+use super::mat4::Mat4;
+use super::aabb::Aabb3;
+
+/// A bounding sphere, given by its center and radius
+///
+/// A BoundingSphere with a negative radius is empty; [`empty`](#method.empty)
+/// returns one that any point or sphere can be added to.
+#[derive(Copy,Clone,PartialEq,Debug)]
+pub struct BoundingSphere {
+    /// The center of the sphere
+    pub center: Vec3<f32>,
+    /// The radius of the sphere
+    pub radius: f32
+}
+
+/// Construct methods for BoundingSphere
+impl BoundingSphere {
+    /// Constructs a new BoundingSphere at `center` with `radius`
+    pub fn new(center: Vec3<f32>, radius: f32) -> Self {
+        BoundingSphere {
+            center,
+            radius
+        }
+    }
+    /// Constructs a new empty BoundingSphere that contains nothing
+    pub fn empty() -> Self {
+        BoundingSphere {
+            center: Vec3::zero(),
+            radius: -1.0
+        }
+    }
+    /// Constructs a BoundingSphere containing every point in `points`
+    ///
+    /// The sphere is centered on the bounding box of the points, so it is not
+    /// always the smallest possible. Returns an empty BoundingSphere if there
+    /// are no points.
+    pub fn from_points(points: &[Vec3<f32>]) -> Self {
+        if points.is_empty() {
+            return Self::empty();
+        }
+
+        let center = Aabb3::from_points(points).center();
+        let radius = points.iter()
+            .map(|p| p.distance_squared(&center))
+            .fold(0.0, f32::max)
+            .sqrt();
+
+        BoundingSphere::new(center, radius)
+    }
+    /// Constructs the smallest BoundingSphere containing the Aabb3 `b`
+    pub fn from_aabb(b: &Aabb3) -> Self {
+        if b.is_empty() {
+            return Self::empty();
+        }
+
+        BoundingSphere::new(b.center(), b.extents().length())
+    }
+}
+
+/// Get methods for BoundingSphere
+impl BoundingSphere {
+    /// Returns true if the BoundingSphere contains nothing
+    pub fn is_empty(&self) -> bool {
+        self.radius < 0.0
+    }
+    /// Returns the smallest Aabb3 containing the BoundingSphere
+    pub fn to_aabb(&self) -> Aabb3 {
+        if self.is_empty() {
+            return Aabb3::empty();
+        }
+
+        let r = self.radius;
+        Aabb3::from_center(self.center, Vec3::from(r, r, r))
+    }
+}
+
+/// Calc methods for BoundingSphere
+impl BoundingSphere {
+    /// Returns true if `p` is inside or on the surface of the BoundingSphere
+    pub fn contains_point(&self, p: Vec3<f32>) -> bool {
+        !self.is_empty() &&
+        p.distance_squared(&self.center) <= self.radius*self.radius
+    }
+    /// Returns true if all of `b` is inside the BoundingSphere
+    ///
+    /// An empty `b` is contained by any BoundingSphere.
+    pub fn contains(&self, b: &Self) -> bool {
+        b.is_empty() ||
+        (!self.is_empty() &&
+         b.center.distance(&self.center) + b.radius <= self.radius)
+    }
+    /// Returns true if the BoundingSphere and `b` overlap or touch
+    pub fn intersects(&self, b: &Self) -> bool {
+        if self.is_empty() || b.is_empty() {
+            return false;
+        }
+
+        let r = self.radius + b.radius;
+        self.center.distance_squared(&b.center) <= r*r
+    }
+    /// Returns true if the BoundingSphere and the Aabb3 `b` overlap or touch
+    pub fn intersects_aabb(&self, b: &Aabb3) -> bool {
+        if self.is_empty() || b.is_empty() {
+            return false;
+        }
+
+        let p = b.closest_point(self.center);
+        p.distance_squared(&self.center) <= self.radius*self.radius
+    }
+    /// Returns the smallest BoundingSphere containing both the
+    /// BoundingSphere and `b`
+    pub fn union(&self, b: &Self) -> Self {
+        if self.contains(b) {
+            return *self;
+        }
+        if b.contains(self) {
+            return *b;
+        }
+
+        let d = b.center - self.center;
+        let dist = d.length();
+        let radius = (dist + self.radius + b.radius)*0.5;
+        let center = self.center + d*((radius - self.radius)/dist);
+
+        BoundingSphere::new(center, radius)
+    }
+    /// Returns a new BoundingSphere containing the BoundingSphere after
+    /// transformation by `m`
+    ///
+    /// `m` should be affine (no projection). Under non-uniform scale the
+    /// radius is scaled by the largest axis, so the result may be larger than
+    /// needed.
+    pub fn transform(&self, m: &Mat4) -> Self {
+        if self.is_empty() {
+            return *self;
+        }
+
+        let a = m.as_array();
+        let scale = Vec3::from(a[0], a[1], a[2]).length_squared()
+            .max(Vec3::from(a[4], a[5], a[6]).length_squared())
+            .max(Vec3::from(a[8], a[9], a[10]).length_squared())
+            .sqrt();
+
+        BoundingSphere::new(m.transform_point(self.center), self.radius*scale)
+    }
+}
+
+/// Mutate methods for BoundingSphere
+impl BoundingSphere {
+    /// Grows the BoundingSphere to contain `p`, moving its center as little
+    /// as needed
+    pub fn expand(&mut self, p: Vec3<f32>) {
+        *self = self.union(&BoundingSphere::new(p, 0.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BoundingSphere;
+    use super::super::aabb::Aabb3;
+    use super::super::vec3::Vec3;
+    use super::super::mat4::Mat4;
+
+    #[test]
+    fn from_points_and_expand() {
+        let points = [Vec3::from(1.0, 0.0, 0.0), Vec3::from(-1.0, 0.0, 0.0),
+                      Vec3::from(0.0, 3.0, 0.0), Vec3::from(0.0, -1.0, 0.5)];
+        let s = BoundingSphere::from_points(&points);
+        for p in points.iter() {
+            assert!(s.contains_point(*p));
+        }
+        assert!(BoundingSphere::from_points(&[]).is_empty());
+
+        let mut s = BoundingSphere::empty();
+        assert!(!s.contains_point(Vec3::zero()));
+        s.expand(Vec3::zero());
+        assert!(s.contains_point(Vec3::zero()));
+        s.expand(Vec3::from(4.0, 0.0, 0.0));
+        assert_eq!(s, BoundingSphere::new(Vec3::from(2.0, 0.0, 0.0), 2.0));
+    }
+    #[test]
+    fn overlap() {
+        let a = BoundingSphere::new(Vec3::zero(), 1.0);
+        let b = BoundingSphere::new(Vec3::from(1.5, 0.0, 0.0), 1.0);
+        let c = BoundingSphere::new(Vec3::from(5.0, 0.0, 0.0), 1.0);
+
+        assert!(a.intersects(&b) && !a.intersects(&c));
+
+        let u = a.union(&c);
+        assert_eq!(u, BoundingSphere::new(Vec3::from(2.5, 0.0, 0.0), 3.5));
+        assert!(u.contains(&a) && u.contains(&c) && !a.contains(&u));
+        assert_eq!(u.union(&b), u);
+
+        let box_near = Aabb3::new(Vec3::from(0.5, 0.5, 0.5), Vec3::one());
+        let box_far = Aabb3::new(Vec3::from(0.8, 0.8, 0.8), Vec3::one());
+        assert!(a.intersects_aabb(&box_near));
+        assert!(!a.intersects_aabb(&box_far));
+
+        assert!(BoundingSphere::from_aabb(&box_far).to_aabb().contains(&box_far));
+    }
+    #[test]
+    fn transform() {
+        let s = BoundingSphere::new(Vec3::from(1.0, 0.0, 0.0), 1.0);
+
+        let mut m = Mat4::new();
+        m.translate(Vec3::from(0.0, 5.0, 0.0));
+        m.scale(Vec3::from(1.0, 3.0, 2.0));
+
+        let t = s.transform(&m);
+        assert!((t.center - Vec3::from(1.0, 5.0, 0.0)).length() < 0.0001);
+        assert!((t.radius - 3.0).abs() < 0.0001);
+    }
+}
@@ -0,0 +1,258 @@
+use std::ops::{Mul, MulAssign};
+
+use super::vec3::Vec3;
+use super::mat4::Mat4;
+use super::quat::Quat;
+
+/// A translation, rotation and scale
+///
+/// Transforms are applied scale first, then rotation and then translation.
+/// A child's world Transform is found by multiplying it by its parent's,
+/// `child * parent`, in the same order as [`Mat4`](struct.Mat4.html).
+#[derive(Copy,Clone,PartialEq,Debug)]
+pub struct Transform {
+    /// The translation (position)
+    pub translation: Vec3<f32>,
+    /// The rotation (orientation)
+    pub rotation: Quat,
+    /// The scale
+    pub scale: Vec3<f32>
+}
+
+/// Construct methods for Transform
+impl Transform {
+    /// Constructs a new Transform that changes nothing
+    pub fn identity() -> Self {
+        Transform::from(Vec3::zero(), Quat::identity(), Vec3::one())
+    }
+    /// Constructs a new Transform and sets it to identity
+    pub fn new() -> Self {
+        Self::identity()
+    }
+    /// Constructs a new Transform from `translation`, `rotation` and `scale`
+    pub fn from(translation: Vec3<f32>, rotation: Quat, scale: Vec3<f32>)
+                -> Self {
+        Transform {
+            translation,
+            rotation,
+            scale
+        }
+    }
+    /// Constructs a new Transform from a Mat4
+    ///
+    /// Shear and projection in the Mat4 can't be represented and are lost.
+    pub fn from_mat4(m: &Mat4) -> Self {
+        let (translation, _, scale) = m.decompose();
+
+        Transform::from(translation, Quat::from_mat4(m), scale)
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::new()
+    }
+}
+
+/// Mutate methods for Transform
+impl Transform {
+    /// Rotates the Transform so its forward axis (+z) points at `target`, with
+    /// its up axis (+y) as close to `up` as possible
+    ///
+    /// Nothing is changed if `target` is at the Transform's translation or
+    /// directly along `up`.
+    pub fn look_at(&mut self, target: Vec3<f32>, up: Vec3<f32>) {
+        let dir = target - self.translation;
+        if dir.length_squared() == 0.0 {
+            return;
+        }
+
+        let f = dir.normalized();
+        let r = up.cross(f);
+        if r.length_squared() < 0.000001 {
+            return;
+        }
+        let r = r.normalized();
+        let u = f.cross(r);
+
+        self.rotation = Quat::from_mat4(&Mat4::from_array([
+            r.x, r.y, r.z, 0.0,
+            u.x, u.y, u.z, 0.0,
+            f.x, f.y, f.z, 0.0,
+            0.0, 0.0, 0.0, 1.0
+        ]));
+    }
+}
+
+/// Calc methods for Transform
+impl Transform {
+    /// Returns the Transform as a Mat4
+    pub fn to_matrix(&self) -> Mat4 {
+        let mut m = self.rotation.to_mat4();
+        m.scale(self.scale);
+        m.set_translation(self.translation);
+        m
+    }
+    /// Returns the inverse of the Transform
+    ///
+    /// The result is exact when the scale is uniform; a non-uniform scale
+    /// combined with a rotation introduces shear that a Transform can't store.
+    pub fn inverse(&self) -> Self {
+        let scale = Vec3::from(1.0/self.scale.x, 1.0/self.scale.y,
+                               1.0/self.scale.z);
+        let rotation = self.rotation.inverse();
+        let translation = (rotation*(self.translation*-1.0))*scale;
+
+        Transform::from(translation, rotation, scale)
+    }
+    /// Transforms a point by the Transform (translation is applied)
+    pub fn transform_point(&self, p: Vec3<f32>) -> Vec3<f32> {
+        self.rotation*(p*self.scale) + self.translation
+    }
+    /// Transforms a direction by the Transform (translation is ignored)
+    pub fn transform_vector(&self, v: Vec3<f32>) -> Vec3<f32> {
+        self.rotation*(v*self.scale)
+    }
+    /// Interpolates from self to `b` by `t` (0 to 1)
+    ///
+    /// Useful for smoothing rendering between two simulation states.
+    pub fn lerp(&self, b: &Self, t: f32) -> Self {
+        Transform::from(
+            self.translation + (b.translation - self.translation)*t,
+            self.rotation.slerp(&b.rotation, t),
+            self.scale + (b.scale - self.scale)*t
+        )
+    }
+}
+
+/// Composes two Transforms; `child * parent` gives the child's world Transform
+///
+/// As with [`inverse`](struct.Transform.html#method.inverse), a rotated child
+/// under a non-uniformly scaled parent can't be represented exactly.
+impl Mul<Self> for Transform {
+    type Output = Self;
+
+    fn mul(self, parent: Self) -> Self {
+        Transform::from(
+            parent.transform_point(self.translation),
+            parent.rotation*self.rotation,
+            parent.scale*self.scale
+        )
+    }
+}
+impl MulAssign<Self> for Transform {
+    fn mul_assign(&mut self, parent: Self) {
+        *self = *self*parent;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Transform;
+    use super::super::vec3::Vec3;
+    use super::super::mat4::Mat4;
+    use super::super::quat::Quat;
+    use super::super::simple::deg;
+
+    fn approx_eq(a: &Mat4, b: &Mat4) -> bool {
+        let (a, b) = (a.as_array(), b.as_array());
+        (0..16).all(|i| (a[i] - b[i]).abs() < 0.0001)
+    }
+    fn approx_eq_vec3(a: Vec3<f32>, b: Vec3<f32>) -> bool {
+        (a.x - b.x).abs() < 0.0001
+            && (a.y - b.y).abs() < 0.0001
+            && (a.z - b.z).abs() < 0.0001
+    }
+    fn sample() -> Transform {
+        Transform::from(
+            Vec3::from(3.0, -2.0, 7.0),
+            Quat::from_euler(Vec3::from(deg(30.0), deg(45.0), deg(60.0))),
+            Vec3::from(2.0, 0.5, 4.0)
+        )
+    }
+
+    #[test]
+    fn to_matrix() {
+        let t = sample();
+
+        let mut m = Mat4::new();
+        m.translate(Vec3::from(3.0, -2.0, 7.0));
+        m.rotate(Vec3::from(deg(30.0), deg(45.0), deg(60.0)));
+        m.scale(Vec3::from(2.0, 0.5, 4.0));
+
+        assert!(approx_eq(&t.to_matrix(), &m));
+
+        let p = Vec3::from(1.0, 2.0, 3.0);
+        assert!(approx_eq_vec3(t.transform_point(p), m.transform_point(p)));
+        assert!(approx_eq_vec3(t.transform_vector(p), m.transform_vector(p)));
+
+        assert!(approx_eq(&Transform::from_mat4(&m).to_matrix(), &m));
+    }
+    #[test]
+    fn inverse() {
+        let mut t = sample();
+        t.scale = Vec3::from(2.0, 2.0, 2.0);
+
+        let p = Vec3::from(1.0, 2.0, 3.0);
+
+        assert!(approx_eq_vec3(t.inverse().transform_point(t.transform_point(p)),
+                               p));
+        assert!(approx_eq(&t.inverse().to_matrix(), &t.to_matrix().inverse()));
+    }
+    #[test]
+    fn hierarchy() {
+        let mut parent = sample();
+        parent.scale = Vec3::from(3.0, 3.0, 3.0);
+        let child = Transform::from(
+            Vec3::from(0.0, 1.0, 0.0),
+            Quat::from_axis_angle(Vec3::from(0.0, 0.0, 1.0), deg(90.0)),
+            Vec3::from(2.0, 2.0, 2.0)
+        );
+
+        let world = child*parent;
+
+        let p = Vec3::from(1.0, 2.0, 3.0);
+        assert!(approx_eq_vec3(world.transform_point(p),
+                               parent.transform_point(child.transform_point(p))));
+        assert!(approx_eq(&world.to_matrix(),
+                          &(child.to_matrix()*parent.to_matrix())));
+
+        let mut w = child;
+        w *= parent;
+        assert!(w == world);
+    }
+    #[test]
+    fn look_at() {
+        let mut t = Transform::new();
+        t.translation = Vec3::from(1.0, 0.0, 0.0);
+
+        t.look_at(Vec3::from(1.0, 0.0, -5.0), Vec3::up());
+
+        let forward = t.transform_vector(Vec3::forward());
+        assert!(approx_eq_vec3(forward, Vec3::from(0.0, 0.0, -1.0)));
+        let up = t.transform_vector(Vec3::up());
+        assert!(approx_eq_vec3(up, Vec3::up()));
+
+        t.look_at(Vec3::from(4.0, 4.0, 0.0), Vec3::up());
+        let forward = t.transform_vector(Vec3::forward());
+        assert!(approx_eq_vec3(forward, Vec3::from(0.6, 0.8, 0.0)));
+    }
+    #[test]
+    fn lerp() {
+        let a = Transform::new();
+        let b = Transform::from(
+            Vec3::from(10.0, 0.0, 0.0),
+            Quat::from_axis_angle(Vec3::up(), deg(90.0)),
+            Vec3::from(3.0, 3.0, 3.0)
+        );
+
+        let half = a.lerp(&b, 0.5);
+
+        assert!(approx_eq_vec3(half.translation, Vec3::from(5.0, 0.0, 0.0)));
+        assert!(approx_eq_vec3(half.scale, Vec3::from(2.0, 2.0, 2.0)));
+        let expected = Quat::from_axis_angle(Vec3::up(), deg(45.0));
+        assert!((half.rotation.dot(&expected).abs() - 1.0).abs() < 0.0001);
+
+        assert!(a.lerp(&b, 0.0) == a);
+    }
+}
@@ -1,13 +1,17 @@
+extern crate num;
+use self::num::Float;
 use std::ops::{Index, IndexMut, Mul, MulAssign, Div, DivAssign, Add, AddAssign,
-               Sub, SubAssign};
+               Sub, SubAssign, Neg};
+
+use super::angle::Angle;
 
 #[derive(Copy,Clone,PartialEq,Eq,PartialOrd,Ord,Debug)]
-/// Vec2 stores 2 elements of any type with specialization for f32
+/// Vec2 stores 2 elements of any type with specialization for floats
 ///
-/// Vec2 is used to store a 2 dimensional point or direction when using f32
-/// as the type. This then provides more useful things (such as
-/// [`dot`](#method.dot) product, and directional constructors such as
-/// [`left`](#method.left) and [`right`](#method.right).
+/// Vec2 is used to store a 2 dimensional point or direction when using a
+/// floating point type (f32 or f64) as the type. This then provides more
+/// useful things (such as [`dot`](#method.dot) product, and directional
+/// constructors such as [`left`](#method.left) and [`right`](#method.right).
 pub struct Vec2<T> {
     /// The x coordinate/element (index 0)
     pub x: T,
@@ -15,13 +19,18 @@ pub struct Vec2<T> {
     pub y: T
 }
 
+/// Vec2 of f32
+pub type Vec2f = Vec2<f32>;
+/// Vec2 of f64
+pub type Vec2d = Vec2<f64>;
+
 /// Generic Vec2 methods
 impl<T> Vec2<T> where T: Copy {
     /// Constructs a new Vec2 from values `x` and `y`
     pub fn from(x: T, y: T) -> Self {
         Vec2 {
-            x: x,
-            y: y
+            x,
+            y
         }
     }
     /// Constructs a new Vec2 from a 2 element array in [x, y] order
@@ -50,73 +59,133 @@ impl<T> Vec2<T> where T: Copy {
 
 include!(concat!(env!("OUT_DIR"), "/vec2_swizzle.rs"));
 
-/// f32 specialized Vec2 methods
-impl Vec2<f32> {
+/// Float specialized Vec2 methods
+impl<T> Vec2<T> where T: Float {
     /// Returns a new Vec2 with x set to 0 and y set to 0
     pub fn zero() -> Self {
-        Vec2::from(0.0f32, 0.0f32)
+        Vec2::from(T::zero(), T::zero())
     }
     /// Returns a new Vec2 with x set to 1 and y set to 1
     pub fn one() -> Self {
-        Vec2::from(1.0f32, 1.0f32)
+        Vec2::from(T::one(), T::one())
     }
     /// Returns a new Vec2 with x set to -1 and y set to 0
     pub fn left() -> Self {
-        Vec2::from(-1.0f32, 0.0f32)
+        Vec2::from(-T::one(), T::zero())
     }
     /// Returns a new Vec2 with x set to 1 and y set to 0
     pub fn right() -> Self {
-        Vec2::from(1.0f32, 0.0f32)
+        Vec2::from(T::one(), T::zero())
     }
     /// Returns a new Vec2 with x set to 0 and y set to -1
     pub fn down() -> Self {
-        Vec2::from(0.0f32, -1.0f32)
+        Vec2::from(T::zero(), -T::one())
     }
     /// Returns a new Vec2 with x set to 0 and y set to 1
     pub fn up() -> Self {
-        Vec2::from(0.0f32, 1.0f32)
+        Vec2::from(T::zero(), T::one())
     }
 
-    /// Calculates the dot product of a Vec2
-    pub fn dot(&self) -> f32 {
-        self.x*self.x + self.y*self.y
+    /// Calculates the dot product of two Vec2s
+    pub fn dot(&self, b: &Self) -> T {
+        self.x*b.x + self.y*b.y
     }
     /// Calculates the length squared of a Vec2
     ///
     /// Note that the actual length of a Vec2 requires a square root operation
     /// that this method purposely avoids for faster comparison of lengths.
-    pub fn length_squared(&self) -> f32 {
-        self.dot()
+    pub fn length_squared(&self) -> T {
+        self.dot(self)
     }
     /// Calculates the length of a Vec2
-    pub fn length(&self) -> f32 {
+    pub fn length(&self) -> T {
         self.length_squared().sqrt()
     }
     /// Returns a new Vec2 with values normalized (unit length)
+    ///
+    /// The zero Vec2 has no direction and produces NaNs; use
+    /// [`try_normalized`](#method.try_normalized) or
+    /// [`normalize_or_zero`](#method.normalize_or_zero) if it may occur.
     pub fn normalized(&self) -> Self {
         let len = self.length();
         Vec2::from(self.x/len, self.y/len)
     }
     /// Mutates self to become normalized (unit length)
     pub fn normalize_self(&mut self) {
+        *self = self.normalized();
+    }
+    /// Returns a new Vec2 with values normalized (unit length), or `None` if
+    /// self has no length to normalize
+    pub fn try_normalized(&self) -> Option<Self> {
         let len = self.length();
-        self.x /= len;
-        self.y /= len;
+        if len == T::zero() || !len.is_finite() {
+            None
+        } else {
+            Some(*self/len)
+        }
+    }
+    /// Returns a new Vec2 with values normalized (unit length), or the zero
+    /// Vec2 if self has no length to normalize
+    pub fn normalize_or_zero(&self) -> Self {
+        self.try_normalized().unwrap_or(Vec2::zero())
     }
     /// Calculates the distance squared between two Vec2s
     ///
     /// Note that the actual distance between two Vec2s requires a square root
     /// operation that this method purposely avoids for faster comparison of
     /// distances.
-    pub fn distance_squared(&self, b: &Self) -> f32 {
+    pub fn distance_squared(&self, b: &Self) -> T {
         (*self - *b).length_squared()
     }
     /// Calculates the distance between two Vec2s
-    pub fn distance(&self, b: &Self) -> f32 {
+    pub fn distance(&self, b: &Self) -> T {
         (*self - *b).length()
     }
+    /// Linearly interpolates from self to `b` by `t` (0 to 1)
+    pub fn lerp(&self, b: &Self, t: T) -> Self {
+        *self + (*b - *self)*t
+    }
+    /// Returns self reflected off of a surface with the (unit length) `normal`
+    pub fn reflect(&self, normal: &Self) -> Self {
+        let d = self.dot(normal);
+        *self - *normal*(d + d)
+    }
+    /// Returns the projection of self onto `b`
+    pub fn project_onto(&self, b: &Self) -> Self {
+        let len_sq = b.length_squared();
+        *b*(self.dot(b)/len_sq)
+    }
+    /// Calculates the (unsigned) Angle between two Vec2s
+    pub fn angle_between(&self, b: &Self) -> Angle<T> {
+        let c = self.dot(b)/(self.length()*b.length());
+        Angle::Radians(c.max(-T::one()).min(T::one()).acos())
+    }
+    /// Returns a new Vec2 with the smaller of each element of two Vec2s
+    pub fn min(&self, b: &Self) -> Self {
+        Vec2::from(self.x.min(b.x), self.y.min(b.y))
+    }
+    /// Returns a new Vec2 with the larger of each element of two Vec2s
+    pub fn max(&self, b: &Self) -> Self {
+        Vec2::from(self.x.max(b.x), self.y.max(b.y))
+    }
+    /// Returns a new Vec2 with each element clamped between the elements of
+    /// `lo` and `hi`
+    pub fn clamp(&self, lo: &Self, hi: &Self) -> Self {
+        self.max(lo).min(hi)
+    }
+    /// Returns a new Vec2 with the absolute value of each element
+    pub fn abs(&self) -> Self {
+        Vec2::from(self.x.abs(), self.y.abs())
+    }
 }
 
+impl<T> Neg for Vec2<T> where T: Neg<Output = T> + Copy {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Vec2::from(-self.x, -self.y)
+    }
+}
 impl<T> Index<usize> for Vec2<T> {
     type Output = T;
 
@@ -241,3 +310,72 @@ impl<T> SubAssign<T> for Vec2<T> where T: SubAssign<T> + Copy {
         self.y -= rhs;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Vec2, Vec2d};
+
+    fn approx_eq(a: Vec2d, b: Vec2d) -> bool {
+        (a.x - b.x).abs() < 0.0000001
+            && (a.y - b.y).abs() < 0.0000001
+    }
+
+    #[test]
+    fn f64_precision() {
+        let v: Vec2d = Vec2::from(3.0, 4.0);
+
+        assert_eq!(v.length(), 5.0f64);
+        assert!(approx_eq(v.normalized()*5.0, v));
+    }
+    #[test]
+    fn neg() {
+        assert!(-Vec2::from(1.0, -2.0) == Vec2::from(-1.0, 2.0));
+    }
+    #[test]
+    fn lerp() {
+        let a = Vec2::from(0.0, 10.0);
+        let b = Vec2::from(10.0, 20.0);
+
+        assert!(approx_eq(a.lerp(&b, 0.25), Vec2::from(2.5, 12.5)));
+        assert!(approx_eq(a.lerp(&b, 1.0), b));
+    }
+    #[test]
+    fn reflect_project() {
+        let v = Vec2::from(1.0, -1.0);
+
+        assert!(approx_eq(v.reflect(&Vec2d::up()), Vec2::from(1.0, 1.0)));
+        assert!(approx_eq(v.project_onto(&Vec2::from(2.0, 0.0)),
+                          Vec2::from(1.0, 0.0)));
+    }
+    #[test]
+    fn angle_between() {
+        let a = Vec2d::right();
+
+        assert!((a.angle_between(&Vec2d::up()).degrees() - 90.0).abs() < 0.0001);
+        assert!((a.angle_between(&Vec2::from(1.0, 1.0)).degrees() - 45.0)
+                .abs() < 0.0001);
+        assert!((a.angle_between(&Vec2d::left()).degrees() - 180.0).abs()
+                < 0.0001);
+    }
+    #[test]
+    fn component_wise() {
+        let a = Vec2::from(1.0, -5.0);
+        let b = Vec2::from(2.0, -6.0);
+
+        assert!(a.min(&b) == Vec2::from(1.0, -6.0));
+        assert!(a.max(&b) == Vec2::from(2.0, -5.0));
+        assert!(a.abs() == Vec2::from(1.0, 5.0));
+        assert!(a.clamp(&Vec2d::zero(), &Vec2d::one()) == Vec2::from(1.0, 0.0));
+    }
+    #[test]
+    fn dot() {
+        let a = Vec2::from(1.0, 2.0);
+        let b = Vec2::from(4.0, -5.0);
+
+        assert_eq!(a.dot(&b), -6.0);
+        assert_eq!(a.dot(&a), a.length_squared());
+        assert_eq!(Vec2d::up().dot(&Vec2d::right()), 0.0);
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/vec2_swizzle_tests.rs"));
+}
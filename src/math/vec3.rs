@@ -1,13 +1,17 @@
+extern crate num;
+use self::num::Float;
 use std::ops::{Index, IndexMut, Mul, MulAssign, Div, DivAssign, Add, AddAssign,
-               Sub, SubAssign};
+               Sub, SubAssign, Neg};
+
+use super::angle::Angle;
 
 #[derive(Copy,Clone,PartialEq,Eq,PartialOrd,Ord,Debug)]
-/// Vec3 stores 3 elements of any type with specialization for f32
+/// Vec3 stores 3 elements of any type with specialization for floats
 ///
-/// Vec3 is used to store a 3 dimensional point or direction when using f32
-/// as the type. This then provides more useful things (such as
-/// [`dot`](#method.dot) product, and directional constructors such as
-/// [`left`](#method.left) and [`right`](#method.right).
+/// Vec3 is used to store a 3 dimensional point or direction when using a
+/// floating point type (f32 or f64) as the type. This then provides more
+/// useful things (such as [`dot`](#method.dot) product, and directional
+/// constructors such as [`left`](#method.left) and [`right`](#method.right).
 pub struct Vec3<T> {
     /// The x coordinate/element (index 0)
     pub x: T,
@@ -17,14 +21,19 @@ pub struct Vec3<T> {
     pub z: T
 }
 
+/// Vec3 of f32
+pub type Vec3f = Vec3<f32>;
+/// Vec3 of f64
+pub type Vec3d = Vec3<f64>;
+
 /// Generic Vec3 methods
 impl<T> Vec3<T> where T: Copy {
     /// Constructs a new Vec3 from values `x`, `y` and `z`
     pub fn from(x: T, y: T, z: T) -> Self {
         Vec3 {
-            x: x,
-            y: y,
-            z: z
+            x,
+            y,
+            z
         }
     }
     /// Constructs a new Vec3 from a 3 element array in [x, y, z] order
@@ -56,44 +65,44 @@ impl<T> Vec3<T> where T: Copy {
 
 include!(concat!(env!("OUT_DIR"), "/vec3_swizzle.rs"));
 
-/// f32 specialized Vec3 methods
-impl Vec3<f32> {
+/// Float specialized Vec3 methods
+impl<T> Vec3<T> where T: Float {
     /// Returns a new Vec3 with x set to 0, y set to 0 and z set to 0
     pub fn zero() -> Self {
-        Vec3::from(0.0f32, 0.0f32, 0.0f32)
+        Vec3::from(T::zero(), T::zero(), T::zero())
     }
     /// Returns a new Vec3 with x set to 1, y set to 1 and z set to 1
     pub fn one() -> Self {
-        Vec3::from(1.0f32, 1.0f32, 1.0f32)
+        Vec3::from(T::one(), T::one(), T::one())
     }
     /// Returns a new Vec3 with x set to -1, y set to 0 and z set to 0
     pub fn left() -> Self {
-        Vec3::from(-1.0f32, 0.0f32, 0.0f32)
+        Vec3::from(-T::one(), T::zero(), T::zero())
     }
     /// Returns a new Vec3 with x set to 1, y set to 0 and z set to 0
     pub fn right() -> Self {
-        Vec3::from(1.0f32, 0.0f32, 0.0f32)
+        Vec3::from(T::one(), T::zero(), T::zero())
     }
     /// Returns a new Vec3 with x set to 0, y set to -1, and z set to 0
     pub fn down() -> Self {
-        Vec3::from(0.0f32, -1.0f32, 0.0f32)
+        Vec3::from(T::zero(), -T::one(), T::zero())
     }
     /// Returns a new Vec3 with x set to 0, y set to 1 and z set to 0
     pub fn up() -> Self {
-        Vec3::from(0.0f32, 1.0f32, 0.0f32)
+        Vec3::from(T::zero(), T::one(), T::zero())
     }
     /// Returns a new Vec3 with x set to 0, y set to 0 and z set to -1
     pub fn backward() -> Self {
-        Vec3::from(0.0f32, 0.0f32, -1.0f32)
+        Vec3::from(T::zero(), T::zero(), -T::one())
     }
     /// Returns a new Vec3 with x set to 0, y set to 0 and z set to 1
     pub fn forward() -> Self {
-        Vec3::from(0.0f32, 0.0f32, 1.0f32)
+        Vec3::from(T::zero(), T::zero(), T::one())
     }
 
-    /// Calculates the dot product of a Vec3
-    pub fn dot(&self) -> f32 {
-        self.x*self.x + self.y*self.y + self.z*self.z
+    /// Calculates the dot product of two Vec3s
+    pub fn dot(&self, b: &Self) -> T {
+        self.x*b.x + self.y*b.y + self.z*b.z
     }
     /// Calculates the cross product of two Vec3s
     pub fn cross(&self, b: Self) -> Self {
@@ -107,39 +116,98 @@ impl Vec3<f32> {
     ///
     /// Note that the actual length of a Vec3 requires a square root operation
     /// that this method purposely avoids for faster comparison of lengths.
-    pub fn length_squared(&self) -> f32 {
-        self.dot()
+    pub fn length_squared(&self) -> T {
+        self.dot(self)
     }
     /// Calculates the length of a Vec3
-    pub fn length(&self) -> f32 {
+    pub fn length(&self) -> T {
         self.length_squared().sqrt()
     }
     /// Returns a new Vec3 with values normalized (unit length)
+    ///
+    /// The zero Vec3 has no direction and produces NaNs; use
+    /// [`try_normalized`](#method.try_normalized) or
+    /// [`normalize_or_zero`](#method.normalize_or_zero) if it may occur.
     pub fn normalized(&self) -> Self {
         let len = self.length();
         Vec3::from(self.x/len, self.y/len, self.z/len)
     }
     /// Mutates self to become normalized (unit length)
     pub fn normalize_self(&mut self) {
+        *self = self.normalized();
+    }
+    /// Returns a new Vec3 with values normalized (unit length), or `None` if
+    /// self has no length to normalize
+    pub fn try_normalized(&self) -> Option<Self> {
         let len = self.length();
-        self.x /= len;
-        self.y /= len;
-        self.z /= len;
+        if len == T::zero() || !len.is_finite() {
+            None
+        } else {
+            Some(*self/len)
+        }
+    }
+    /// Returns a new Vec3 with values normalized (unit length), or the zero
+    /// Vec3 if self has no length to normalize
+    pub fn normalize_or_zero(&self) -> Self {
+        self.try_normalized().unwrap_or(Vec3::zero())
     }
     /// Calculates the distance squared between two Vec3s
     ///
     /// Note that the actual distance between two Vec3s requires a square root
     /// operation that this method purposely avoids for faster comparison of
     /// distances.
-    pub fn distance_squared(&self, b: &Self) -> f32 {
+    pub fn distance_squared(&self, b: &Self) -> T {
         (*self - *b).length_squared()
     }
     /// Calculates the distance between two Vec3s
-    pub fn distance(&self, b: &Self) -> f32 {
+    pub fn distance(&self, b: &Self) -> T {
         (*self - *b).length()
     }
+    /// Linearly interpolates from self to `b` by `t` (0 to 1)
+    pub fn lerp(&self, b: &Self, t: T) -> Self {
+        *self + (*b - *self)*t
+    }
+    /// Returns self reflected off of a surface with the (unit length) `normal`
+    pub fn reflect(&self, normal: &Self) -> Self {
+        let d = self.dot(normal);
+        *self - *normal*(d + d)
+    }
+    /// Returns the projection of self onto `b`
+    pub fn project_onto(&self, b: &Self) -> Self {
+        let len_sq = b.length_squared();
+        *b*(self.dot(b)/len_sq)
+    }
+    /// Calculates the (unsigned) Angle between two Vec3s
+    pub fn angle_between(&self, b: &Self) -> Angle<T> {
+        let c = self.dot(b)/(self.length()*b.length());
+        Angle::Radians(c.max(-T::one()).min(T::one()).acos())
+    }
+    /// Returns a new Vec3 with the smaller of each element of two Vec3s
+    pub fn min(&self, b: &Self) -> Self {
+        Vec3::from(self.x.min(b.x), self.y.min(b.y), self.z.min(b.z))
+    }
+    /// Returns a new Vec3 with the larger of each element of two Vec3s
+    pub fn max(&self, b: &Self) -> Self {
+        Vec3::from(self.x.max(b.x), self.y.max(b.y), self.z.max(b.z))
+    }
+    /// Returns a new Vec3 with each element clamped between the elements of
+    /// `lo` and `hi`
+    pub fn clamp(&self, lo: &Self, hi: &Self) -> Self {
+        self.max(lo).min(hi)
+    }
+    /// Returns a new Vec3 with the absolute value of each element
+    pub fn abs(&self) -> Self {
+        Vec3::from(self.x.abs(), self.y.abs(), self.z.abs())
+    }
 }
 
+impl<T> Neg for Vec3<T> where T: Neg<Output = T> + Copy {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Vec3::from(-self.x, -self.y, -self.z)
+    }
+}
 impl<T> Index<usize> for Vec3<T> {
     type Output = T;
 
@@ -274,3 +342,84 @@ impl<T> SubAssign<T> for Vec3<T> where T: SubAssign<T> + Copy {
         self.z -= rhs;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Vec3, Vec3d};
+
+    fn approx_eq(a: Vec3d, b: Vec3d) -> bool {
+        (a.x - b.x).abs() < 0.0000001
+            && (a.y - b.y).abs() < 0.0000001
+            && (a.z - b.z).abs() < 0.0000001
+    }
+
+    #[test]
+    fn f64_precision() {
+        let v: Vec3d = Vec3::from(3.0, 4.0, 12.0);
+
+        assert_eq!(v.length(), 13.0f64);
+        assert!(approx_eq(v.normalized()*13.0, v));
+        assert!(approx_eq(Vec3d::up().cross(Vec3d::right()),
+                          Vec3d::backward()));
+    }
+    #[test]
+    fn neg() {
+        assert!(-Vec3::from(1.0, -2.0, 3.0) == Vec3::from(-1.0, 2.0, -3.0));
+    }
+    #[test]
+    fn lerp() {
+        let a = Vec3::from(0.0, 10.0, -4.0);
+        let b = Vec3::from(10.0, 20.0, 4.0);
+
+        assert!(approx_eq(a.lerp(&b, 0.25), Vec3::from(2.5, 12.5, -2.0)));
+        assert!(approx_eq(a.lerp(&b, 1.0), b));
+    }
+    #[test]
+    fn reflect_project() {
+        let v = Vec3::from(1.0, -1.0, 0.0);
+
+        assert!(approx_eq(v.reflect(&Vec3d::up()), Vec3::from(1.0, 1.0, 0.0)));
+        assert!(approx_eq(v.project_onto(&Vec3::from(2.0, 0.0, 0.0)),
+                          Vec3::from(1.0, 0.0, 0.0)));
+    }
+    #[test]
+    fn angle_between() {
+        let a = Vec3d::right();
+
+        assert!((a.angle_between(&Vec3d::up()).degrees() - 90.0).abs() < 0.0001);
+        assert!((a.angle_between(&Vec3::from(1.0, 1.0, 0.0)).degrees() - 45.0)
+                .abs() < 0.0001);
+        assert!(a.angle_between(&a).degrees().abs() < 0.0001);
+    }
+    #[test]
+    fn component_wise() {
+        let a = Vec3::from(1.0, -5.0, 3.0);
+        let b = Vec3::from(2.0, -6.0, 0.0);
+
+        assert!(a.min(&b) == Vec3::from(1.0, -6.0, 0.0));
+        assert!(a.max(&b) == Vec3::from(2.0, -5.0, 3.0));
+        assert!(a.abs() == Vec3::from(1.0, 5.0, 3.0));
+        assert!(a.clamp(&Vec3d::zero(), &Vec3d::one())
+                == Vec3::from(1.0, 0.0, 1.0));
+    }
+    #[test]
+    fn dot() {
+        let a = Vec3::from(1.0, 2.0, 3.0);
+        let b = Vec3::from(4.0, -5.0, 6.0);
+
+        assert_eq!(a.dot(&b), 12.0);
+        assert_eq!(a.dot(&a), a.length_squared());
+        assert_eq!(Vec3d::up().dot(&Vec3d::right()), 0.0);
+    }
+    #[test]
+    fn safe_normalize() {
+        assert!(Vec3d::zero().try_normalized().is_none());
+        assert!(Vec3d::zero().normalize_or_zero() == Vec3d::zero());
+
+        let v = Vec3::from(0.0, 0.0, 5.0);
+        assert!(v.try_normalized() == Some(Vec3d::forward()));
+        assert!(v.normalize_or_zero() == Vec3d::forward());
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/vec3_swizzle_tests.rs"));
+}
@@ -1,13 +1,17 @@
+extern crate num;
+use self::num::Float;
 use std::ops::{Index, IndexMut, Mul, MulAssign, Div, DivAssign, Add, AddAssign,
-               Sub, SubAssign};
+               Sub, SubAssign, Neg};
+
+use super::angle::Angle;
 
 #[derive(Copy,Clone,PartialEq,Eq,PartialOrd,Ord,Debug)]
-/// Vec4 stores 4 elements of any type with specialization for f32
+/// Vec4 stores 4 elements of any type with specialization for floats
 ///
-/// Vec4 is used to store a 4 dimensional point or direction when using f32
-/// as the type. This then provides more useful things (such as
-/// [`dot`](#method.dot) product, and directional constructors such as
-/// [`left`](#method.left) and [`right`](#method.right).
+/// Vec4 is used to store a 4 dimensional point or direction when using a
+/// floating point type (f32 or f64) as the type. This then provides more
+/// useful things (such as [`dot`](#method.dot) product, and directional
+/// constructors such as [`left`](#method.left) and [`right`](#method.right).
 pub struct Vec4<T> {
     /// The x coordinate/element (index 0)
     pub x: T,
@@ -19,15 +23,20 @@ pub struct Vec4<T> {
     pub w: T
 }
 
+/// Vec4 of f32
+pub type Vec4f = Vec4<f32>;
+/// Vec4 of f64
+pub type Vec4d = Vec4<f64>;
+
 /// Generic Vec4 methods
 impl<T> Vec4<T> where T: Copy {
     /// Constructs a new Vec4 from values `x`, `y`, `z` and `w`
     pub fn from(x: T, y: T, z: T, w: T) -> Self {
         Vec4 {
-            x: x,
-            y: y,
-            z: z,
-            w: w
+            x,
+            y,
+            z,
+            w
         }
     }
     /// Constructs a new Vec4 from a 4 element array in [x, y, z, w] order
@@ -62,104 +71,164 @@ impl<T> Vec4<T> where T: Copy {
 
 include!(concat!(env!("OUT_DIR"), "/vec4_swizzle.rs"));
 
-/// f32 specialized Vec4 methods
-impl Vec4<f32> {
+/// Float specialized Vec4 methods
+impl<T> Vec4<T> where T: Float {
     /// Returns a new Vec4 with x set to 0, y set to 0, z set to 0 and w set to
     /// 1
     pub fn zero() -> Self {
-        Vec4::from(0.0f32, 0.0f32, 0.0f32, 1.0f32)
+        Vec4::from(T::zero(), T::zero(), T::zero(), T::one())
     }
     /// Returns a new Vec4 with x set to 1, y set to 1, z set to 1 and w set to
     /// 1
     pub fn one() -> Self {
-        Vec4::from(1.0f32, 1.0f32, 1.0f32, 1.0f32)
+        Vec4::from(T::one(), T::one(), T::one(), T::one())
     }
     /// Returns a new Vec4 with x set to -1, y set to 0, z set to 0 and w set to
     /// 1
     pub fn left() -> Self {
-        Vec4::from(-1.0f32, 0.0f32, 0.0f32, 1.0f32)
+        Vec4::from(-T::one(), T::zero(), T::zero(), T::one())
     }
     /// Returns a new Vec4 with x set to 1, y set to 0, z set to 0 and w set to
     /// 1
     pub fn right() -> Self {
-        Vec4::from(1.0f32, 0.0f32, 0.0f32, 1.0f32)
+        Vec4::from(T::one(), T::zero(), T::zero(), T::one())
     }
     /// Returns a new Vec4 with x set to 0, y set to -1, z set to 0 and w set to
     /// 1
     pub fn down() -> Self {
-        Vec4::from(0.0f32, -1.0f32, 0.0f32, 1.0f32)
+        Vec4::from(T::zero(), -T::one(), T::zero(), T::one())
     }
     /// Returns a new Vec4 with x set to 0, y set to 1, z set to 0 and w set to
     /// 1
     pub fn up() -> Self {
-        Vec4::from(0.0f32, 1.0f32, 0.0f32, 1.0f32)
+        Vec4::from(T::zero(), T::one(), T::zero(), T::one())
     }
     /// Returns a new Vec4 with x set to 0, y set to 0, z set to -1 and w set to
     /// 1
     pub fn backward() -> Self {
-        Vec4::from(0.0f32, 0.0f32, -1.0f32, 1.0f32)
+        Vec4::from(T::zero(), T::zero(), -T::one(), T::one())
     }
     /// Returns a new Vec4 with x set to 0, y set to 0, z set to 1 and w set to
     /// 1
     pub fn forward() -> Self {
-        Vec4::from(0.0f32, 0.0f32, 1.0f32, 1.0f32)
+        Vec4::from(T::zero(), T::zero(), T::one(), T::one())
     }
 
-    /// Calculates the dot product of a Vec4
-    pub fn dot(&self) -> f32 {
-        self.x*self.x + self.y*self.y + self.z*self.z + self.w*self.w
+    /// Calculates the dot product of two Vec4s
+    pub fn dot(&self, b: &Self) -> T {
+        self.x*b.x + self.y*b.y + self.z*b.z + self.w*b.w
     }
     /// Calculates the cross product of three Vec4s
-    fn cross(&self, b: Self, c: Self) -> Self {
+    ///
+    /// The result is perpendicular to all three Vec4s.
+    pub fn cross(&self, b: Self, c: Self) -> Self {
         Vec4::from(
-             (self.y*(b.z*c.w - c.z*b.w) - self.z*(b.y*c.w - c.y*b.w)
-                + self.w*(b.y*c.z - c.y*b.z)),
+            self.y*(b.z*c.w - c.z*b.w) - self.z*(b.y*c.w - c.y*b.w)
+                + self.w*(b.y*c.z - c.y*b.z),
             -(self.x*(b.z*c.w - c.z*b.w) - self.z*(b.x*c.w - c.x*b.w)
                 + self.w*(b.x*c.z - c.x*b.z)),
-             (self.x*(b.y*c.w - c.y*b.w) - self.y*(b.x*c.w - c.x*b.w)
-                + self.w*(b.x*c.y - c.x*b.y)),
+            self.x*(b.y*c.w - c.y*b.w) - self.y*(b.x*c.w - c.x*b.w)
+                + self.w*(b.x*c.y - c.x*b.y),
             -(self.x*(b.y*c.z - c.y*b.z) - self.y*(b.x*c.z - c.x*b.z)
-                + self.w*(b.x*c.y - c.y*b.y))
+                + self.z*(b.x*c.y - c.x*b.y))
         )
     }
     /// Calculates the length squared of a Vec4
     ///
     /// Note that the actual length of a Vec4 requires a square root operation
     /// that this method purposely avoids for faster comparison of lengths.
-    pub fn length_squared(&self) -> f32 {
-        self.dot()
+    pub fn length_squared(&self) -> T {
+        self.dot(self)
     }
     /// Calculates the length of a Vec4
-    pub fn length(&self) -> f32 {
+    pub fn length(&self) -> T {
         self.length_squared().sqrt()
     }
     /// Returns a new Vec4 with values normalized (unit length)
+    ///
+    /// The zero Vec4 has no direction and produces NaNs; use
+    /// [`try_normalized`](#method.try_normalized) or
+    /// [`normalize_or_zero`](#method.normalize_or_zero) if it may occur.
     pub fn normalized(&self) -> Self {
         let len = self.length();
         Vec4::from(self.x/len, self.y/len, self.z/len, self.w/len)
     }
     /// Mutates self to become normalized (unit length)
     pub fn normalize_self(&mut self) {
+        *self = self.normalized();
+    }
+    /// Returns a new Vec4 with values normalized (unit length), or `None` if
+    /// self has no length to normalize
+    pub fn try_normalized(&self) -> Option<Self> {
         let len = self.length();
-        self.x /= len;
-        self.y /= len;
-        self.z /= len;
-        self.w /= len;
+        if len == T::zero() || !len.is_finite() {
+            None
+        } else {
+            Some(*self/len)
+        }
+    }
+    /// Returns a new Vec4 with values normalized (unit length), or the zero
+    /// Vec4 if self has no length to normalize
+    pub fn normalize_or_zero(&self) -> Self {
+        self.try_normalized().unwrap_or(Vec4::zero())
     }
     /// Calculates the distance squared between two Vec4s
     ///
     /// Note that the actual distance between two Vec4s requires a square root
     /// operation that this method purposely avoids for faster comparison of
     /// distances.
-    pub fn distance_squared(&self, b: &Self) -> f32 {
+    pub fn distance_squared(&self, b: &Self) -> T {
         (*self - *b).length_squared()
     }
     /// Calculates the distance between two Vec4s
-    pub fn distance(&self, b: &Self) -> f32 {
+    pub fn distance(&self, b: &Self) -> T {
         (*self - *b).length()
     }
+    /// Linearly interpolates from self to `b` by `t` (0 to 1)
+    pub fn lerp(&self, b: &Self, t: T) -> Self {
+        *self + (*b - *self)*t
+    }
+    /// Returns self reflected off of a surface with the (unit length) `normal`
+    pub fn reflect(&self, normal: &Self) -> Self {
+        let d = self.dot(normal);
+        *self - *normal*(d + d)
+    }
+    /// Returns the projection of self onto `b`
+    pub fn project_onto(&self, b: &Self) -> Self {
+        let len_sq = b.length_squared();
+        *b*(self.dot(b)/len_sq)
+    }
+    /// Calculates the (unsigned) Angle between two Vec4s
+    pub fn angle_between(&self, b: &Self) -> Angle<T> {
+        let c = self.dot(b)/(self.length()*b.length());
+        Angle::Radians(c.max(-T::one()).min(T::one()).acos())
+    }
+    /// Returns a new Vec4 with the smaller of each element of two Vec4s
+    pub fn min(&self, b: &Self) -> Self {
+        Vec4::from(self.x.min(b.x), self.y.min(b.y), self.z.min(b.z), self.w.min(b.w))
+    }
+    /// Returns a new Vec4 with the larger of each element of two Vec4s
+    pub fn max(&self, b: &Self) -> Self {
+        Vec4::from(self.x.max(b.x), self.y.max(b.y), self.z.max(b.z), self.w.max(b.w))
+    }
+    /// Returns a new Vec4 with each element clamped between the elements of
+    /// `lo` and `hi`
+    pub fn clamp(&self, lo: &Self, hi: &Self) -> Self {
+        self.max(lo).min(hi)
+    }
+    /// Returns a new Vec4 with the absolute value of each element
+    pub fn abs(&self) -> Self {
+        Vec4::from(self.x.abs(), self.y.abs(), self.z.abs(), self.w.abs())
+    }
 }
 
+impl<T> Neg for Vec4<T> where T: Neg<Output = T> + Copy {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Vec4::from(-self.x, -self.y, -self.z, -self.w)
+    }
+}
 impl<T> Index<usize> for Vec4<T> {
     type Output = T;
 
@@ -314,3 +383,93 @@ impl<T> SubAssign<T> for Vec4<T> where T: SubAssign<T> + Copy {
         self.w -= rhs;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Vec4, Vec4d};
+
+    fn approx_eq(a: Vec4d, b: Vec4d) -> bool {
+        (a.x - b.x).abs() < 0.0000001
+            && (a.y - b.y).abs() < 0.0000001
+            && (a.z - b.z).abs() < 0.0000001
+            && (a.w - b.w).abs() < 0.0000001
+    }
+
+    #[test]
+    fn cross() {
+        let a = Vec4::from(1.0, 2.0, 3.0, 4.0f32);
+        let b = Vec4::from(-2.0, 0.5, 1.0, 3.0);
+        let c = Vec4::from(0.0, 7.0, -1.0, 2.0);
+
+        let r = a.cross(b, c);
+
+        assert!(r.dot(&a).abs() < 0.0001);
+        assert!(r.dot(&b).abs() < 0.0001);
+        assert!(r.dot(&c).abs() < 0.0001);
+
+        // crossing the x, y and z axes gives the negative w axis
+        let x = Vec4::from(1.0, 0.0, 0.0, 0.0);
+        let y = Vec4::from(0.0, 1.0, 0.0, 0.0);
+        let z = Vec4::from(0.0, 0.0, 1.0, 0.0);
+        assert!(x.cross(y, z) == Vec4::from(0.0, 0.0, 0.0, -1.0));
+    }
+    #[test]
+    fn dot() {
+        let a = Vec4::from(1.0, 2.0, 3.0, 4.0);
+        let b = Vec4::from(1.0, -1.0, 1.0, -1.0);
+
+        assert_eq!(a.dot(&b), -2.0f32);
+        assert!(Vec4::from(0.0f32, 0.0, 0.0, 0.0).try_normalized().is_none());
+    }
+    #[test]
+    fn f64_precision() {
+        let v: Vec4d = Vec4::from(1.0, 2.0, 2.0, 4.0);
+
+        assert_eq!(v.length(), 5.0f64);
+        assert!(approx_eq(v.normalized()*5.0, v));
+    }
+    #[test]
+    fn neg() {
+        assert!(-Vec4::from(1.0, -2.0, 3.0, -4.0)
+                == Vec4::from(-1.0, 2.0, -3.0, 4.0));
+    }
+    #[test]
+    fn lerp() {
+        let a = Vec4::from(0.0, 10.0, -4.0, 1.0);
+        let b = Vec4::from(10.0, 20.0, 4.0, 1.0);
+
+        assert!(approx_eq(a.lerp(&b, 0.25), Vec4::from(2.5, 12.5, -2.0, 1.0)));
+        assert!(approx_eq(a.lerp(&b, 1.0), b));
+    }
+    #[test]
+    fn reflect_project() {
+        let v = Vec4::from(1.0, -1.0, 0.0, 2.0);
+        let up = Vec4::from(0.0, 1.0, 0.0, 0.0);
+
+        assert!(approx_eq(v.reflect(&up), Vec4::from(1.0, 1.0, 0.0, 2.0)));
+        assert!(approx_eq(v.project_onto(&Vec4::from(0.0, 0.0, 0.0, 3.0)),
+                          Vec4::from(0.0, 0.0, 0.0, 2.0)));
+    }
+    #[test]
+    fn angle_between() {
+        let a: Vec4d = Vec4::from(1.0, 0.0, 0.0, 0.0);
+        let b = Vec4::from(0.0, 0.0, 0.0, 1.0);
+
+        assert!((a.angle_between(&b).degrees() - 90.0).abs() < 0.0001);
+        assert!((a.angle_between(&(a + b)).degrees() - 45.0).abs() < 0.0001);
+        assert!(a.angle_between(&a).degrees().abs() < 0.0001);
+    }
+    #[test]
+    fn component_wise() {
+        let a = Vec4::from(1.0, -5.0, 3.0, 0.5);
+        let b = Vec4::from(2.0, -6.0, 0.0, 0.25);
+
+        assert!(a.min(&b) == Vec4::from(1.0, -6.0, 0.0, 0.25));
+        assert!(a.max(&b) == Vec4::from(2.0, -5.0, 3.0, 0.5));
+        assert!(a.abs() == Vec4::from(1.0, 5.0, 3.0, 0.5));
+        assert!(a.clamp(&Vec4::from(0.0, 0.0, 0.0, 0.0), &Vec4d::one())
+                == Vec4::from(1.0, 0.0, 1.0, 0.5));
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/vec4_swizzle_tests.rs"));
+}
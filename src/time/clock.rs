@@ -0,0 +1,110 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// A source of monotonic time for [`Time`](struct.Time.html)
+pub trait Clock {
+    /// Returns the time since the Clock's starting point
+    ///
+    /// The result must never go backwards.
+    fn now(&self) -> Duration;
+}
+
+/// A Clock reading the operating system's monotonic clock
+#[derive(Copy,Clone,Debug)]
+pub struct SystemClock {
+    start: Instant
+}
+
+/// Construct methods for SystemClock
+impl SystemClock {
+    /// Constructs a new SystemClock starting at the current time
+    pub fn new() -> Self {
+        SystemClock {
+            start: Instant::now()
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        SystemClock::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// A Clock that only moves when told to, for deterministic tests and replays
+///
+/// Clones share the same time, so a test can keep one to advance after
+/// handing another to a [`Time`](struct.Time.html).
+#[derive(Clone,Debug)]
+pub struct ManualClock {
+    now: Rc<Cell<Duration>>
+}
+
+/// Construct methods for ManualClock
+impl ManualClock {
+    /// Constructs a new ManualClock starting at zero
+    pub fn new() -> Self {
+        ManualClock {
+            now: Rc::new(Cell::new(Duration::new(0, 0)))
+        }
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        ManualClock::new()
+    }
+}
+
+/// Mutate methods for ManualClock
+impl ManualClock {
+    /// Moves the ManualClock, and every clone of it, forward by `d`
+    pub fn advance(&self, d: Duration) {
+        self.now.set(self.now.get() + d);
+    }
+    /// Moves the ManualClock, and every clone of it, forward by `secs`
+    /// seconds
+    pub fn advance_secs(&self, secs: f64) {
+        self.advance(Duration::from_nanos((secs*1_000_000_000.0) as u64));
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::{Clock, SystemClock, ManualClock};
+
+    #[test]
+    fn system() {
+        let clock = SystemClock::new();
+
+        let a = clock.now();
+        let b = clock.now();
+        assert!(a <= b);
+    }
+    #[test]
+    fn manual() {
+        let clock = ManualClock::new();
+        let shared = clock.clone();
+        assert_eq!(clock.now(), Duration::new(0, 0));
+
+        shared.advance(Duration::from_millis(250));
+        assert_eq!(clock.now(), Duration::from_millis(250));
+
+        clock.advance_secs(0.5);
+        assert_eq!(shared.now(), Duration::from_millis(750));
+    }
+}
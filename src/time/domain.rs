@@ -0,0 +1,121 @@
+/// A time line with its own scale and pause state, advanced by a parent
+///
+/// Every [`Time`](struct.Time.html) has a root TimeDomain advanced by its
+/// clock, and can hold named child domains (such as "gameplay" and "ui")
+/// advanced by the root's scaled time. Pausing or slowing a child leaves the
+/// others running, while pausing the root stops them all.
+#[derive(Copy,Clone,PartialEq,Debug)]
+pub struct TimeDomain {
+    scale: f64,
+    paused: bool,
+    delta: f64,
+    elapsed: f64,
+    unscaled_delta: f64,
+    unscaled_elapsed: f64
+}
+
+/// Construct methods for TimeDomain
+impl TimeDomain {
+    /// Constructs a new running TimeDomain with a scale of 1
+    pub fn new() -> Self {
+        TimeDomain {
+            scale: 1.0,
+            paused: false,
+            delta: 0.0,
+            elapsed: 0.0,
+            unscaled_delta: 0.0,
+            unscaled_elapsed: 0.0
+        }
+    }
+}
+
+impl Default for TimeDomain {
+    fn default() -> Self {
+        TimeDomain::new()
+    }
+}
+
+/// Get methods for TimeDomain
+impl TimeDomain {
+    /// Returns the speed of the TimeDomain relative to its parent
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+    /// Returns true if the TimeDomain is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+    /// Returns the scaled time of the prior advance, which is 0 while paused
+    pub fn delta(&self) -> f64 {
+        self.delta
+    }
+    /// Returns the scaled time advanced since creation
+    pub fn elapsed(&self) -> f64 {
+        self.elapsed
+    }
+    /// Returns the parent's time of the prior advance, ignoring scale and
+    /// pause
+    pub fn unscaled_delta(&self) -> f64 {
+        self.unscaled_delta
+    }
+    /// Returns the parent's time advanced since creation, ignoring scale and
+    /// pause
+    pub fn unscaled_elapsed(&self) -> f64 {
+        self.unscaled_elapsed
+    }
+}
+
+/// Mutate methods for TimeDomain
+impl TimeDomain {
+    /// Sets the speed of the TimeDomain relative to its parent; 0.5 is half
+    /// speed and 2 is double
+    ///
+    /// Negative scales are treated as 0.
+    pub fn set_scale(&mut self, scale: f64) {
+        self.scale = scale.max(0.0);
+    }
+    /// Pauses the TimeDomain, so its scaled time stops
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+    /// Resumes the TimeDomain after [`pause`](#method.pause)
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+    /// Advances the TimeDomain by `delta` seconds of its parent's time
+    pub fn advance(&mut self, delta: f64) {
+        self.unscaled_delta = delta;
+        self.unscaled_elapsed += delta;
+
+        self.delta = if self.paused { 0.0 } else { delta*self.scale };
+        self.elapsed += self.delta;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TimeDomain;
+
+    #[test]
+    fn scale_and_pause() {
+        let mut d = TimeDomain::new();
+
+        d.advance(0.5);
+        assert_eq!((d.delta(), d.elapsed()), (0.5, 0.5));
+
+        d.set_scale(0.5);
+        d.advance(0.5);
+        assert_eq!((d.delta(), d.elapsed()), (0.25, 0.75));
+        assert_eq!((d.unscaled_delta(), d.unscaled_elapsed()), (0.5, 1.0));
+
+        d.pause();
+        d.advance(0.5);
+        assert!(d.is_paused());
+        assert_eq!((d.delta(), d.elapsed()), (0.0, 0.75));
+        assert_eq!(d.unscaled_elapsed(), 1.5);
+
+        d.resume();
+        d.set_scale(-1.0);
+        assert_eq!(d.scale(), 0.0);
+    }
+}
@@ -0,0 +1,169 @@
+use super::{Time, Clock};
+
+/// Splits variable frame time into fixed size simulation steps
+///
+/// Each update adds the frame's scaled
+/// [`delta`](struct.Time.html#method.delta) to an accumulator and returns how
+/// many whole steps it now holds; run the simulation that many times, then
+/// draw using [`alpha`](#method.alpha) to blend between the last two simulated
+/// states. Pausing the Time pauses the steps too. A frame never runs more than
+/// `max_steps` steps, so a long stall drops time instead of falling further
+/// and further behind.
+#[derive(Copy,Clone,PartialEq,Debug)]
+pub struct FixedStep {
+    step: f64,
+    max_steps: u32,
+    accumulator: f64,
+    ticks: u64
+}
+
+/// Construct methods for FixedStep
+impl FixedStep {
+    /// Constructs a new FixedStep running `tick_rate` steps per second, with
+    /// at most 5 steps per update
+    ///
+    /// # Panics
+    /// Panics if `tick_rate` is not a positive number.
+    pub fn new(tick_rate: f64) -> Self {
+        assert!(tick_rate > 0.0 && tick_rate.is_finite(),
+                "FixedStep tick rate must be positive!");
+
+        FixedStep {
+            step: 1.0/tick_rate,
+            max_steps: 5,
+            accumulator: 0.0,
+            ticks: 0
+        }
+    }
+    /// Sets the most steps a single update may return
+    pub fn with_max_steps(mut self, max_steps: u32) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+}
+
+/// Get methods for FixedStep
+impl FixedStep {
+    /// Returns the number of steps per second
+    pub fn tick_rate(&self) -> f64 {
+        1.0/self.step
+    }
+    /// Returns the length of one step in seconds
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+    /// Returns the most steps a single update may return
+    pub fn max_steps(&self) -> u32 {
+        self.max_steps
+    }
+    /// Returns the total number of steps run since creation
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+    /// Returns how far between the last step and the next one the current
+    /// time is, from 0 up to (but not including) 1
+    pub fn alpha(&self) -> f64 {
+        self.accumulator/self.step
+    }
+}
+
+/// Mutate methods for FixedStep
+impl FixedStep {
+    /// Sets the number of steps per second, keeping the progress towards the
+    /// next step
+    ///
+    /// # Panics
+    /// Panics if `tick_rate` is not a positive number.
+    pub fn set_tick_rate(&mut self, tick_rate: f64) {
+        assert!(tick_rate > 0.0 && tick_rate.is_finite(),
+                "FixedStep tick rate must be positive!");
+
+        let alpha = self.alpha();
+        self.step = 1.0/tick_rate;
+        self.accumulator = alpha*self.step;
+    }
+    /// Sets the most steps a single update may return
+    pub fn set_max_steps(&mut self, max_steps: u32) {
+        self.max_steps = max_steps;
+    }
+    /// Adds the time since the prior update of `time` and returns the number
+    /// of steps to run
+    ///
+    /// Call this once per frame, after updating `time`.
+    pub fn update<C: Clock>(&mut self, time: &Time<C>) -> u32 {
+        self.advance(time.delta())
+    }
+    /// Adds `delta` seconds and returns the number of steps to run
+    pub fn advance(&mut self, delta: f64) -> u32 {
+        self.accumulator += delta.max(0.0);
+
+        let mut steps = 0;
+        while self.accumulator >= self.step {
+            if steps == self.max_steps {
+                // too far behind to catch up; drop the rest
+                self.accumulator %= self.step;
+                break;
+            }
+            self.accumulator -= self.step;
+            steps += 1;
+        }
+
+        self.ticks += steps as u64;
+        steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FixedStep;
+    use super::super::ManualClock;
+
+    #[test]
+    fn accumulate() {
+        let mut fixed = FixedStep::new(4.0);
+        assert_eq!(fixed.step(), 0.25);
+
+        assert_eq!(fixed.advance(0.125), 0);
+        assert_eq!(fixed.alpha(), 0.5);
+
+        assert_eq!(fixed.advance(0.5), 2);
+        assert_eq!(fixed.alpha(), 0.5);
+        assert_eq!(fixed.ticks(), 2);
+
+        assert_eq!(fixed.advance(0.125), 1);
+        assert_eq!(fixed.alpha(), 0.0);
+        assert_eq!(fixed.ticks(), 3);
+    }
+    #[test]
+    fn max_steps() {
+        let mut fixed = FixedStep::new(4.0).with_max_steps(3);
+
+        // a 10.125 second stall only runs 3 steps and keeps the remainder
+        assert_eq!(fixed.advance(10.125), 3);
+        assert_eq!(fixed.alpha(), 0.5);
+        assert_eq!(fixed.ticks(), 3);
+
+        assert_eq!(fixed.advance(0.125), 1);
+    }
+    #[test]
+    fn set_tick_rate() {
+        let mut fixed = FixedStep::new(4.0);
+        fixed.advance(0.125);
+
+        fixed.set_tick_rate(2.0);
+        assert_eq!(fixed.step(), 0.5);
+        assert_eq!(fixed.alpha(), 0.5);
+        assert_eq!(fixed.advance(0.25), 1);
+    }
+    #[test]
+    fn update() {
+        let clock = ManualClock::new();
+        let mut time = ::Time::with_clock(clock.clone());
+        let mut fixed = FixedStep::new(4.0);
+
+        clock.advance_secs(0.625);
+        time.update();
+        assert_eq!(fixed.update(&time), 2);
+        assert_eq!(fixed.alpha(), 0.5);
+    }
+}
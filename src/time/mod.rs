@@ -0,0 +1,312 @@
+//! Time module
+
+use std::collections::HashMap;
+use std::time;
+
+mod clock;
+pub use self::clock::{Clock, SystemClock, ManualClock};
+mod domain;
+pub use self::domain::TimeDomain;
+mod fixed;
+pub use self::fixed::FixedStep;
+mod timer;
+pub use self::timer::{Timers, TimerHandle};
+mod stats;
+pub use self::stats::{FrameStats, FrameLimiter};
+
+/// Provides elapsed time and delta (change-in) time between updates
+///
+/// The time structure reads a [`Clock`](trait.Clock.html), the real
+/// [`SystemClock`](struct.SystemClock.html) unless another is given, and
+/// stores three readings (when the Time structure is created, the previous
+/// frame time, and the most recent frame time) and provides the
+/// [`delta`](#method.delta) method to calculate the time since the previous
+/// update.
+///
+/// The time can be slowed, sped up or paused with
+/// [`set_scale`](#method.set_scale) and [`pause`](#method.pause), which
+/// affect [`delta`](#method.delta) and [`elapsed`](#method.elapsed) but not
+/// their `unscaled_` versions. Named child
+/// [`TimeDomain`](struct.TimeDomain.html)s follow the scaled time with their
+/// own scale and pause on top.
+pub struct Time<C: Clock = SystemClock> {
+    clock: C,
+    start: time::Duration,
+    now: time::Duration,
+    root: TimeDomain,
+    domains: HashMap<String, TimeDomain>
+}
+/// Construct methods for Time
+impl Time {
+    /// Constructs a new Time structure, initializing its members to the current
+    /// time
+    pub fn new() -> Self {
+        Self::with_clock(SystemClock::new())
+    }
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Time::new()
+    }
+}
+/// Construct methods for Time
+impl<C: Clock> Time<C> {
+    /// Constructs a new Time structure reading `clock`, initializing its
+    /// members to the clock's current time
+    pub fn with_clock(clock: C) -> Self {
+        let now = clock.now();
+        Time {
+            clock,
+            start: now,
+            now,
+            root: TimeDomain::new(),
+            domains: HashMap::new()
+        }
+    }
+}
+/// Get methods for Time
+impl<C: Clock> Time<C> {
+    /// Returns the Clock this Time structure reads
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+    /// Returns the speed of the scaled time
+    pub fn scale(&self) -> f64 {
+        self.root.scale()
+    }
+    /// Returns true if the scaled time is paused
+    pub fn is_paused(&self) -> bool {
+        self.root.is_paused()
+    }
+    /// Returns the child TimeDomain called `name`, if there is one
+    pub fn domain(&self, name: &str) -> Option<&TimeDomain> {
+        self.domains.get(name)
+    }
+}
+/// Mutate methods for Time
+impl<C: Clock> Time<C> {
+    /// Updates the Time structure
+    /// # Notes
+    /// Make sure to call this often (normally once per main loop) to ensure the
+    /// times are accurate.
+    pub fn update(&mut self) {
+        // a clock behind an earlier advance counts as no time passing
+        let now = self.clock.now().max(self.now);
+        let delta = now - self.now;
+
+        self.step(now, (delta.as_secs() as f64)
+                       + ((delta.subsec_nanos() as f64) / 1_000_000_000.0));
+    }
+    /// Updates the Time structure as if `delta` seconds had passed, without
+    /// reading the clock
+    ///
+    /// Used to run at a simulated rate or to replay recorded frame times.
+    /// The next [`update`](#method.update) measures from whichever is later,
+    /// the clock or the advanced time. A `delta` that is negative, infinite
+    /// or NaN counts as 0.
+    pub fn advance(&mut self, delta: f64) {
+        let delta = if delta.is_finite() { delta.max(0.0) } else { 0.0 };
+        let now = self.now + time::Duration::from_nanos(
+            (delta*1_000_000_000.0) as u64);
+
+        self.step(now, delta);
+    }
+    /// Sets the speed of the scaled time; 0.5 is half speed and 2 is double
+    ///
+    /// Negative scales are treated as 0.
+    pub fn set_scale(&mut self, scale: f64) {
+        self.root.set_scale(scale);
+    }
+    /// Pauses the scaled time, and with it every child TimeDomain
+    pub fn pause(&mut self) {
+        self.root.pause();
+    }
+    /// Resumes the scaled time after [`pause`](#method.pause)
+    pub fn resume(&mut self) {
+        self.root.resume();
+    }
+    /// Adds a child TimeDomain called `name`, starting from the next update,
+    /// and returns it
+    ///
+    /// If there already is one called `name` it is returned instead.
+    pub fn add_domain(&mut self, name: &str) -> &mut TimeDomain {
+        self.domains.entry(name.to_string()).or_default()
+    }
+    /// Returns the child TimeDomain called `name` for changing its scale or
+    /// pausing it, if there is one
+    pub fn domain_mut(&mut self, name: &str) -> Option<&mut TimeDomain> {
+        self.domains.get_mut(name)
+    }
+    /// Removes the child TimeDomain called `name` and returns it, if there
+    /// is one
+    pub fn remove_domain(&mut self, name: &str) -> Option<TimeDomain> {
+        self.domains.remove(name)
+    }
+
+    fn step(&mut self, now: time::Duration, delta: f64) {
+        self.now = now;
+
+        self.root.advance(delta);
+        let delta = self.root.delta();
+        for domain in self.domains.values_mut() {
+            domain.advance(delta);
+        }
+    }
+}
+/// Calc methods for Time
+impl<C: Clock> Time<C> {
+    /// Calculates the scaled elapsed time since creation of this Time
+    /// structure as an f64
+    /// # Notes
+    /// This does **not** return the actual current time. Instead, it returns
+    /// the time it took from the creation of this [`Time`](struct.Time.html)
+    /// structure to the previous update. This keeps your actual time one update
+    /// behind of *actual*.
+    pub fn elapsed(&self) -> f64 {
+        self.root.elapsed()
+    }
+    /// Calculates the scaled time since the prior update of this Time
+    /// structure as an f64; this is 0 while paused
+    pub fn delta(&self) -> f64 {
+        self.root.delta()
+    }
+    /// Calculates the elapsed time since creation of this Time structure as an
+    /// f64, ignoring scale and pause
+    pub fn unscaled_elapsed(&self) -> f64 {
+        let dur = self.now - self.start;
+        (dur.as_secs() as f64)
+        + ((dur.subsec_nanos() as f64) / 1_000_000_000.0)
+    }
+    /// Calculates the time since the prior update of this Time structure as an
+    /// f64, ignoring scale and pause
+    pub fn unscaled_delta(&self) -> f64 {
+        self.root.unscaled_delta()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time as stdtime;
+    use super::ManualClock;
+
+    #[test]
+    fn construct() {
+        #[allow(unused_variables)]
+        let time = ::Time::new();
+    }
+    #[test]
+    fn elapsed() {
+        let clock = ManualClock::new();
+        let mut time = ::Time::with_clock(clock.clone());
+
+        let a = time.elapsed();
+
+        clock.advance(stdtime::Duration::new(0, 5000));
+
+        time.update();
+
+        let b = time.elapsed();
+
+        assert!(a < b);
+        assert_eq!(b, 5000.0/1_000_000_000.0);
+    }
+    #[test]
+    fn delta() {
+        let clock = ManualClock::new();
+        let mut time = ::Time::with_clock(clock.clone());
+
+        time.update();
+
+        clock.advance(stdtime::Duration::new(0, 5000));
+
+        time.update();
+
+        assert_eq!(time.delta(), 5000.0/1_000_000_000.0);
+
+        time.update();
+
+        assert_eq!(time.delta(), 0.0);
+    }
+    #[test]
+    fn system_clock() {
+        let mut time = ::Time::new();
+
+        let a = time.elapsed();
+        time.update();
+        let b = time.elapsed();
+
+        assert!(a <= b);
+        assert!(time.delta() >= 0.0);
+    }
+    #[test]
+    fn scale_and_pause() {
+        let clock = ManualClock::new();
+        let mut time = ::Time::with_clock(clock.clone());
+
+        time.set_scale(0.5);
+        clock.advance_secs(1.0);
+        time.update();
+        assert_eq!((time.delta(), time.elapsed()), (0.5, 0.5));
+        assert_eq!((time.unscaled_delta(), time.unscaled_elapsed()), (1.0, 1.0));
+
+        time.pause();
+        clock.advance_secs(1.0);
+        time.update();
+        assert!(time.is_paused());
+        assert_eq!((time.delta(), time.elapsed()), (0.0, 0.5));
+        assert_eq!(time.unscaled_elapsed(), 2.0);
+    }
+    #[test]
+    fn domains() {
+        let clock = ManualClock::new();
+        let mut time = ::Time::with_clock(clock.clone());
+
+        time.add_domain("gameplay").set_scale(2.0);
+        time.add_domain("ui");
+
+        clock.advance_secs(1.0);
+        time.update();
+        assert_eq!(time.domain("gameplay").unwrap().delta(), 2.0);
+        assert_eq!(time.domain("ui").unwrap().delta(), 1.0);
+
+        // a pause menu stops gameplay but the ui keeps animating
+        time.domain_mut("gameplay").unwrap().pause();
+        clock.advance_secs(1.0);
+        time.update();
+        assert_eq!(time.domain("gameplay").unwrap().elapsed(), 2.0);
+        assert_eq!(time.domain("ui").unwrap().elapsed(), 2.0);
+
+        // pausing the root stops every domain
+        time.pause();
+        clock.advance_secs(1.0);
+        time.update();
+        assert_eq!(time.domain("ui").unwrap().delta(), 0.0);
+        assert_eq!(time.domain("ui").unwrap().unscaled_delta(), 0.0);
+
+        assert!(time.remove_domain("ui").is_some());
+        assert!(time.domain("ui").is_none());
+    }
+    #[test]
+    fn advance() {
+        let clock = ManualClock::new();
+        let mut time = ::Time::with_clock(clock.clone());
+
+        time.set_scale(2.0);
+        time.advance(1.0/60.0);
+        assert_eq!(time.unscaled_delta(), 1.0/60.0);
+        assert_eq!(time.delta(), 2.0/60.0);
+
+        // the clock has not caught up with the advanced time yet
+        clock.advance_secs(0.5);
+        time.update();
+        assert!((time.unscaled_delta() - (0.5 - 1.0/60.0)).abs() < 0.000001);
+        assert!((time.unscaled_elapsed() - 0.5).abs() < 0.000001);
+
+        time.advance(f64::INFINITY);
+        assert_eq!(time.unscaled_delta(), 0.0);
+        time.advance(f64::NAN);
+        assert_eq!(time.delta(), 0.0);
+        assert!((time.unscaled_elapsed() - 0.5).abs() < 0.000001);
+    }
+}
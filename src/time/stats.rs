@@ -0,0 +1,345 @@
+use std::collections::VecDeque;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::{Time, Clock};
+
+/// Keeps a rolling window of frame times and reports frame rate statistics
+///
+/// Frames are recorded in seconds, normally once per frame with
+/// [`update`](#method.update). Once the window is full each new frame
+/// replaces the oldest one. Every frame rate is 0 while no frames are
+/// recorded.
+#[derive(Clone,PartialEq,Debug)]
+pub struct FrameStats {
+    frames: VecDeque<f64>,
+    capacity: usize
+}
+
+/// Construct methods for FrameStats
+impl FrameStats {
+    /// Constructs a new FrameStats keeping the most recent `capacity` frames
+    ///
+    /// # Panics
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "FrameStats capacity must not be 0!");
+
+        FrameStats {
+            frames: VecDeque::with_capacity(capacity),
+            capacity
+        }
+    }
+}
+
+/// Get methods for FrameStats
+impl FrameStats {
+    /// Returns the number of frames recorded, up to the capacity
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+    /// Returns true if no frames are recorded
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+    /// Returns the most frames kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    /// Returns the recorded frame times, oldest first
+    pub fn frame_times(&self) -> Vec<f64> {
+        self.frames.iter().cloned().collect()
+    }
+}
+
+/// Calc methods for FrameStats
+impl FrameStats {
+    /// Calculates the average frame time in seconds
+    pub fn average_frame_time(&self) -> f64 {
+        if self.frames.is_empty() {
+            return 0.0;
+        }
+        self.total()/self.frames.len() as f64
+    }
+    /// Calculates the average frames per second
+    pub fn average_fps(&self) -> f64 {
+        fps(self.frames.len(), self.total())
+    }
+    /// Calculates the frames per second of the slowest frame
+    pub fn min_fps(&self) -> f64 {
+        fps(1, self.frames.iter().cloned().fold(0.0, f64::max))
+    }
+    /// Calculates the frames per second of the fastest frame
+    pub fn max_fps(&self) -> f64 {
+        if self.frames.is_empty() {
+            return 0.0;
+        }
+        fps(1, self.frames.iter().cloned().fold(f64::INFINITY, f64::min))
+    }
+    /// Calculates the average frames per second over the slowest `fraction`
+    /// of the frames, always including at least the slowest one
+    ///
+    /// A `fraction` of 0.01 gives the "1% low" frame rate.
+    pub fn low_fps(&self, fraction: f64) -> f64 {
+        let sorted = self.sorted();
+        let count = ((sorted.len() as f64*fraction).ceil() as usize)
+            .max(1)
+            .min(sorted.len());
+
+        let slowest = &sorted[sorted.len() - count..];
+        fps(slowest.len(), slowest.iter().sum())
+    }
+    /// Calculates the average frames per second over the slowest 1% of the
+    /// frames
+    pub fn one_percent_low(&self) -> f64 {
+        self.low_fps(0.01)
+    }
+    /// Calculates the average frames per second over the slowest 0.1% of the
+    /// frames
+    pub fn point_one_percent_low(&self) -> f64 {
+        self.low_fps(0.001)
+    }
+    /// Calculates the frame time that `percentile` (from 0 to 1) of the
+    /// frames are as fast as or faster than
+    pub fn percentile_frame_time(&self, percentile: f64) -> f64 {
+        let sorted = self.sorted();
+        if sorted.is_empty() {
+            return 0.0;
+        }
+
+        let rank = (sorted.len() as f64*percentile.clamp(0.0, 1.0)).ceil();
+        sorted[(rank as usize).max(1) - 1]
+    }
+    /// Counts the frames into `bins` buckets of equal width from 0 to
+    /// `max_frame_time` seconds
+    ///
+    /// Frames slower than `max_frame_time` are counted in the last bucket.
+    pub fn histogram(&self, bins: usize, max_frame_time: f64) -> Vec<usize> {
+        let mut counts = vec![0; bins];
+        if bins == 0 {
+            return counts;
+        }
+
+        let width = max_frame_time/bins as f64;
+        for &t in self.frames.iter() {
+            let i = (t/width) as usize;
+            counts[i.min(bins - 1)] += 1;
+        }
+        counts
+    }
+
+    fn total(&self) -> f64 {
+        self.frames.iter().sum()
+    }
+    fn sorted(&self) -> Vec<f64> {
+        let mut sorted = self.frame_times();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        sorted
+    }
+}
+
+/// Mutate methods for FrameStats
+impl FrameStats {
+    /// Records the unscaled time since the prior update of `time` as a frame
+    ///
+    /// Call this once per frame, after updating `time`.
+    pub fn update<C: Clock>(&mut self, time: &Time<C>) {
+        self.record(time.unscaled_delta());
+    }
+    /// Records a frame that took `frame_time` seconds
+    ///
+    /// Frame times that are not finite, such as NaN, are ignored.
+    pub fn record(&mut self, frame_time: f64) {
+        if !frame_time.is_finite() {
+            return;
+        }
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame_time);
+    }
+    /// Forgets every recorded frame
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+}
+
+fn fps(frames: usize, seconds: f64) -> f64 {
+    if frames == 0 || seconds <= 0.0 {
+        0.0
+    } else {
+        frames as f64/seconds
+    }
+}
+
+/// Holds the frame rate down to a target by waiting at the end of each frame
+///
+/// The wait sleeps for most of the remaining time and then spins for the
+/// last part, as sleeping alone often wakes up late. A frame that already
+/// took longer than the target is not waited on, and does not make the next
+/// frame shorter to catch up.
+#[derive(Copy,Clone,PartialEq,Debug)]
+pub struct FrameLimiter {
+    target: Duration,
+    spin: Duration,
+    last: Option<Instant>
+}
+
+/// Construct methods for FrameLimiter
+impl FrameLimiter {
+    /// Constructs a new FrameLimiter holding frames to `fps` per second, and
+    /// spinning for the last millisecond of each wait
+    ///
+    /// # Panics
+    /// Panics if `fps` is not a positive number.
+    pub fn new(fps: f64) -> Self {
+        FrameLimiter {
+            target: frame_time(fps),
+            spin: Duration::from_millis(1),
+            last: None
+        }
+    }
+    /// Sets how long the end of each wait spins instead of sleeping
+    pub fn with_spin(mut self, spin: Duration) -> Self {
+        self.spin = spin;
+        self
+    }
+}
+
+/// Get methods for FrameLimiter
+impl FrameLimiter {
+    /// Returns the time each frame is held to
+    pub fn target_frame_time(&self) -> Duration {
+        self.target
+    }
+}
+
+/// Mutate methods for FrameLimiter
+impl FrameLimiter {
+    /// Sets the frames per second to hold to
+    ///
+    /// # Panics
+    /// Panics if `fps` is not a positive number.
+    pub fn set_target_fps(&mut self, fps: f64) {
+        self.target = frame_time(fps);
+    }
+    /// Waits until the target frame time has passed since the prior wait
+    ///
+    /// Call this once per frame, at the end of the frame. The first call
+    /// returns straight away.
+    pub fn wait(&mut self) {
+        let now = Instant::now();
+        let deadline = match self.last {
+            Some(last) => last + self.target,
+            None => now
+        };
+
+        if deadline <= now {
+            self.last = Some(now);
+            return;
+        }
+
+        let sleep = deadline - now;
+        if sleep > self.spin {
+            thread::sleep(sleep - self.spin);
+        }
+        while Instant::now() < deadline {
+            thread::yield_now();
+        }
+
+        self.last = Some(deadline);
+    }
+}
+
+fn frame_time(fps: f64) -> Duration {
+    assert!(fps > 0.0 && fps.is_finite(),
+            "FrameLimiter target must be positive!");
+    Duration::from_nanos((1_000_000_000.0/fps) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+    use super::{FrameStats, FrameLimiter};
+    use super::super::ManualClock;
+
+    fn approx_eq(a: f64, b: f64) -> bool {
+        (a - b).abs() < 0.0001
+    }
+
+    #[test]
+    fn rates() {
+        let mut stats = FrameStats::new(1000);
+        assert_eq!(stats.average_fps(), 0.0);
+        assert_eq!(stats.min_fps(), 0.0);
+        assert_eq!(stats.max_fps(), 0.0);
+        assert_eq!(stats.one_percent_low(), 0.0);
+
+        for _ in 0..198 {
+            stats.record(0.0125);
+        }
+        stats.record(0.0625);
+        stats.record(0.125);
+
+        assert_eq!(stats.len(), 200);
+        assert!(approx_eq(stats.average_fps(), 200.0/(198.0*0.0125 + 0.1875)));
+        assert!(approx_eq(stats.min_fps(), 8.0));
+        assert!(approx_eq(stats.max_fps(), 80.0));
+
+        // the slowest 2 frames, and then just the slowest one
+        assert!(approx_eq(stats.one_percent_low(), 2.0/0.1875));
+        assert!(approx_eq(stats.point_one_percent_low(), 8.0));
+
+        assert_eq!(stats.percentile_frame_time(0.5), 0.0125);
+        assert_eq!(stats.percentile_frame_time(0.995), 0.0625);
+        assert_eq!(stats.percentile_frame_time(1.0), 0.125);
+    }
+    #[test]
+    fn rolling_window() {
+        let mut stats = FrameStats::new(3);
+        for &t in [0.5, 0.25, 0.25, 0.25].iter() {
+            stats.record(t);
+        }
+
+        assert_eq!(stats.frame_times(), vec![0.25, 0.25, 0.25]);
+        assert!(approx_eq(stats.average_fps(), 4.0));
+
+        let clock = ManualClock::new();
+        let mut time = ::Time::with_clock(clock.clone());
+        time.set_scale(0.5);
+        clock.advance_secs(0.125);
+        time.update();
+        stats.update(&time);
+        assert_eq!(stats.frame_times(), vec![0.25, 0.25, 0.125]);
+
+        stats.record(f64::NAN);
+        stats.record(f64::INFINITY);
+        assert_eq!(stats.frame_times(), vec![0.25, 0.25, 0.125]);
+        assert_eq!(stats.percentile_frame_time(1.0), 0.25);
+
+        stats.clear();
+        assert!(stats.is_empty());
+    }
+    #[test]
+    fn histogram() {
+        let mut stats = FrameStats::new(10);
+        for &t in [0.005, 0.015, 0.016, 0.035, 0.5].iter() {
+            stats.record(t);
+        }
+
+        assert_eq!(stats.histogram(4, 0.04), vec![1, 2, 0, 2]);
+        assert!(stats.histogram(0, 0.04).is_empty());
+    }
+    #[test]
+    fn limiter() {
+        let mut limiter = FrameLimiter::new(500.0);
+        assert_eq!(limiter.target_frame_time(), Duration::from_millis(2));
+
+        let start = Instant::now();
+        limiter.wait();
+        for _ in 0..3 {
+            limiter.wait();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(6));
+    }
+}
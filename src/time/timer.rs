@@ -0,0 +1,296 @@
+use super::{Time, Clock};
+
+/// Identifies a timer added to [`Timers`](struct.Timers.html), for polling or
+/// cancelling it
+#[derive(Copy,Clone,PartialEq,Eq,Hash,Debug)]
+pub struct TimerHandle(u64);
+
+/// The shortest interval of a repeating timer, in seconds
+const MIN_INTERVAL: f64 = 0.001;
+/// The most times a repeating timer fires in one update
+const MAX_FIRES: u64 = 1000;
+
+struct Timer {
+    handle: TimerHandle,
+    remaining: f64,
+    interval: Option<f64>,
+    callback: Option<Box<dyn FnMut()>>
+}
+
+/// Runs one-shot and repeating timers
+///
+/// Timers count down the scaled [`delta`](struct.Time.html#method.delta), so
+/// they slow down, speed up and pause along with the Time driving them. When
+/// a timer fires its callback is called, if it has one, and its handle is
+/// listed by [`fired`](#method.fired) until the next update.
+pub struct Timers {
+    next: u64,
+    timers: Vec<Timer>,
+    fired: Vec<TimerHandle>
+}
+
+/// Construct methods for Timers
+impl Timers {
+    /// Constructs a new Timers with no timers
+    pub fn new() -> Self {
+        Timers {
+            next: 0,
+            timers: Vec::new(),
+            fired: Vec::new()
+        }
+    }
+}
+
+impl Default for Timers {
+    fn default() -> Self {
+        Timers::new()
+    }
+}
+
+/// Get methods for Timers
+impl Timers {
+    /// Returns the number of timers still waiting to fire
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+    /// Returns true if there are no timers waiting to fire
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+    /// Returns true if the timer `handle` is still waiting to fire; one-shot
+    /// timers stop after firing, and repeating ones when cancelled
+    pub fn is_active(&self, handle: TimerHandle) -> bool {
+        self.timers.iter().any(|t| t.handle == handle)
+    }
+    /// Returns the seconds until the timer `handle` next fires, or `None` if
+    /// it is not active
+    pub fn remaining(&self, handle: TimerHandle) -> Option<f64> {
+        self.timers.iter()
+            .find(|t| t.handle == handle)
+            .map(|t| t.remaining)
+    }
+    /// Returns the handles of the timers fired by the prior update, once for
+    /// each time they fired, in the order they were added
+    pub fn fired(&self) -> &[TimerHandle] {
+        &self.fired
+    }
+    /// Returns true if the timer `handle` fired during the prior update
+    pub fn has_fired(&self, handle: TimerHandle) -> bool {
+        self.fired.contains(&handle)
+    }
+}
+
+/// Mutate methods for Timers
+impl Timers {
+    /// Adds a timer that fires once, `delay` seconds from now
+    pub fn after(&mut self, delay: f64) -> TimerHandle {
+        self.add(delay, None, None)
+    }
+    /// Adds a timer that fires every `interval` seconds, starting `interval`
+    /// seconds from now
+    ///
+    /// # Panics
+    /// Panics if `interval` is shorter than a millisecond.
+    pub fn every(&mut self, interval: f64) -> TimerHandle {
+        assert!(interval >= MIN_INTERVAL,
+                "Timer interval must be at least a millisecond!");
+        self.add(interval, Some(interval), None)
+    }
+    /// Adds a timer that calls `f` once, `delay` seconds from now
+    pub fn after_fn<F>(&mut self, delay: f64, f: F) -> TimerHandle
+        where F: FnMut() + 'static {
+        self.add(delay, None, Some(Box::new(f)))
+    }
+    /// Adds a timer that calls `f` every `interval` seconds, starting
+    /// `interval` seconds from now
+    ///
+    /// # Panics
+    /// Panics if `interval` is shorter than a millisecond.
+    pub fn every_fn<F>(&mut self, interval: f64, f: F) -> TimerHandle
+        where F: FnMut() + 'static {
+        assert!(interval >= MIN_INTERVAL,
+                "Timer interval must be at least a millisecond!");
+        self.add(interval, Some(interval), Some(Box::new(f)))
+    }
+    /// Stops the timer `handle` from firing again; returns false if it was
+    /// not active
+    pub fn cancel(&mut self, handle: TimerHandle) -> bool {
+        let len = self.timers.len();
+        self.timers.retain(|t| t.handle != handle);
+        self.timers.len() != len
+    }
+    /// Stops every timer
+    pub fn clear(&mut self) {
+        self.timers.clear();
+    }
+    /// Counts down the time since the prior update of `time`, firing any
+    /// timers that run out
+    ///
+    /// Call this once per frame, after updating `time`.
+    pub fn update<C: Clock>(&mut self, time: &Time<C>) {
+        self.advance(time.delta())
+    }
+    /// Counts down `delta` seconds, firing any timers that run out
+    ///
+    /// A repeating timer fires once for each interval that passed, up to a
+    /// thousand times; any further intervals are skipped. A `delta` that is
+    /// negative, infinite or NaN counts as 0.
+    pub fn advance(&mut self, delta: f64) {
+        self.fired.clear();
+        let delta = if delta.is_finite() { delta.max(0.0) } else { 0.0 };
+
+        for t in self.timers.iter_mut() {
+            t.remaining -= delta;
+            if t.remaining > 0.0 {
+                continue;
+            }
+
+            let fires = match t.interval {
+                Some(interval) => {
+                    let n = (-t.remaining/interval).floor() + 1.0;
+                    t.remaining += n*interval;
+                    (n as u64).min(MAX_FIRES)
+                },
+                None => 1
+            };
+            for _ in 0..fires {
+                self.fired.push(t.handle);
+                if let Some(ref mut f) = t.callback {
+                    f();
+                }
+            }
+        }
+
+        self.timers.retain(|t| t.interval.is_some() || t.remaining > 0.0);
+    }
+
+    fn add(&mut self, delay: f64, interval: Option<f64>,
+           callback: Option<Box<dyn FnMut()>>) -> TimerHandle {
+        let handle = TimerHandle(self.next);
+        self.next += 1;
+
+        self.timers.push(Timer {
+            handle,
+            remaining: delay.max(0.0),
+            interval,
+            callback
+        });
+        handle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use super::Timers;
+    use super::super::ManualClock;
+
+    #[test]
+    fn one_shot() {
+        let mut timers = Timers::new();
+        let t = timers.after(1.0);
+
+        timers.advance(0.75);
+        assert!(!timers.has_fired(t));
+        assert_eq!(timers.remaining(t), Some(0.25));
+
+        timers.advance(0.5);
+        assert!(timers.has_fired(t));
+        assert!(!timers.is_active(t));
+        assert!(timers.is_empty());
+
+        timers.advance(1.0);
+        assert!(timers.fired().is_empty());
+    }
+    #[test]
+    fn repeating() {
+        let mut timers = Timers::new();
+        let t = timers.every(0.5);
+
+        timers.advance(0.25);
+        assert!(timers.fired().is_empty());
+
+        // a long frame fires once for each interval that passed
+        timers.advance(1.5);
+        assert_eq!(timers.fired(), &[t, t, t]);
+        assert_eq!(timers.remaining(t), Some(0.25));
+
+        assert!(timers.cancel(t));
+        assert!(!timers.cancel(t));
+        timers.advance(1.0);
+        assert!(timers.fired().is_empty());
+
+        // landing exactly on an interval fires for it
+        let t = timers.every(0.25);
+        timers.advance(0.75);
+        assert_eq!(timers.fired(), &[t, t, t]);
+        assert_eq!(timers.remaining(t), Some(0.25));
+    }
+    #[test]
+    fn bad_delta() {
+        let mut timers = Timers::new();
+        let t = timers.every(0.001);
+
+        timers.advance(f64::INFINITY);
+        timers.advance(f64::NAN);
+        timers.advance(-1.0);
+        assert!(timers.fired().is_empty());
+        assert_eq!(timers.remaining(t), Some(0.001));
+
+        // a huge frame fires a bounded number of times
+        timers.advance(1e9);
+        assert_eq!(timers.fired().len(), 1000);
+        assert!(timers.remaining(t).unwrap() > 0.0);
+    }
+    #[test]
+    #[should_panic]
+    fn tiny_interval() {
+        Timers::new().every(1e-12);
+    }
+    #[test]
+    fn callbacks() {
+        let count = Rc::new(Cell::new(0));
+        let mut timers = Timers::new();
+
+        let c = count.clone();
+        timers.every_fn(1.0, move || c.set(c.get() + 1));
+        let c = count.clone();
+        let once = timers.after_fn(1.5, move || c.set(c.get() + 10));
+
+        timers.advance(1.0);
+        assert_eq!(count.get(), 1);
+        timers.advance(1.0);
+        assert_eq!(count.get(), 12);
+        assert!(timers.has_fired(once));
+        timers.advance(1.0);
+        assert_eq!(count.get(), 13);
+        assert_eq!(timers.len(), 1);
+    }
+    #[test]
+    fn scale_and_pause() {
+        let clock = ManualClock::new();
+        let mut time = ::Time::with_clock(clock.clone());
+        let mut timers = Timers::new();
+        let t = timers.after(1.0);
+
+        time.set_scale(0.5);
+        clock.advance_secs(1.0);
+        time.update();
+        timers.update(&time);
+        assert_eq!(timers.remaining(t), Some(0.5));
+
+        time.pause();
+        clock.advance_secs(10.0);
+        time.update();
+        timers.update(&time);
+        assert_eq!(timers.remaining(t), Some(0.5));
+
+        time.resume();
+        time.set_scale(1.0);
+        clock.advance_secs(0.5);
+        time.update();
+        timers.update(&time);
+        assert!(timers.has_fired(t));
+    }
+}